@@ -0,0 +1,43 @@
+// Benchmarks every registered day against its real input. Days can be picked by passing their numbers,
+// e.g. `cargo bench -- 4 6`, and AOC_BENCH_RUNS overrides how many runs each part gets.
+use aoc_2024::benchmark;
+use aoc_2024::input::{read_input, InputSource};
+use aoc_2024::registry;
+use aoc_2024::solution::parts;
+
+const RUNS_VARIABLE: &str = "AOC_BENCH_RUNS";
+
+fn main() {
+    let selected_days = std::env::args()
+        .skip(1)
+        .filter_map(|a| a.parse::<u32>().ok())
+        .collect::<Vec<u32>>();
+
+    let runs = std::env::var(RUNS_VARIABLE)
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(benchmark::DEFAULT_RUNS);
+
+    let days = registry::days()
+        .into_iter()
+        .filter(|d| selected_days.is_empty() || selected_days.contains(&d.day()))
+        .collect::<Vec<_>>();
+
+    let mut benchmarks = Vec::new();
+
+    for day in days {
+        let real_data = read_input(&InputSource::Data { is_real: true, day: day.day(), order: None });
+
+        let benchmark = match real_data {
+            Ok(text) => benchmark::benchmark_day(day, &parts(), runs, |_| Ok(text.clone())),
+            Err(error) => Err(error.into()),
+        };
+
+        match benchmark {
+            Ok(benchmark) => benchmarks.push(benchmark),
+            Err(error) => eprintln!("Skipping day {}: {}", day.day(), error),
+        }
+    }
+
+    println!("{}", benchmark::format_table(&benchmarks));
+}
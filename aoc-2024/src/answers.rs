@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AocError, AocResult};
+use crate::input::{day_folder_name, InputError, InputSource};
+use crate::registry;
+use crate::solution::{Answer, Day, Part};
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+const HEADER: &str = "# Verified answers, checked by `aoc-2024 run` and updated by `aoc-2024 record`";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct AnswerKey {
+    pub day: u32,
+    pub part: Part,
+    pub is_real: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AnswerStatus {
+    Pass,
+    Fail { expected: Answer },
+    Unknown,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct AnswerStore {
+    answers: BTreeMap<AnswerKey, Answer>,
+}
+
+impl AnswerKey {
+    // Solves the part on the default input of the day
+    pub fn solve(&self) -> AocResult<Answer> {
+        registry::find_solved_day(self.day)?.run(self.part, self.is_real)
+    }
+}
+
+impl AnswerStatus {
+    pub fn compare(answer: Answer, expected: Option<Answer>) -> Self {
+        match expected {
+            None => AnswerStatus::Unknown,
+            Some(expected) if expected == answer => AnswerStatus::Pass,
+            Some(expected) => AnswerStatus::Fail { expected },
+        }
+    }
+}
+
+impl fmt::Display for AnswerStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswerStatus::Pass => write!(f, "PASS"),
+            AnswerStatus::Fail { expected } => write!(f, "FAIL (expected {})", expected),
+            AnswerStatus::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl AnswerStore {
+    pub fn parse(text: &str) -> AocResult<Self> {
+        let mut store = AnswerStore::default();
+        let mut current_day = None;
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current_day = Some(parse_day_section(section).map_err(|e| e.at_line(index, line))?);
+                continue;
+            }
+
+            let Some(day) = current_day else {
+                return Err(AocError::parse("Answer found before any [dec_NN] section").at_line(index, line));
+            };
+
+            let (key, answer) = parse_answer_line(day, line).map_err(|e| e.at_line(index, line))?;
+            store.answers.insert(key, answer);
+        }
+
+        Ok(store)
+    }
+
+    // A missing file is treated as an empty store, so the first recorded answer creates it
+    pub fn load(path: &Path) -> AocResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(error) => Err(InputError::Unreadable { path: path.to_path_buf(), error }.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> AocResult<()> {
+        std::fs::write(path, self.to_toml())
+            .map_err(|error| InputError::Unwritable { path: path.to_path_buf(), error }.into())
+    }
+
+    pub fn get(&self, key: &AnswerKey) -> Option<Answer> {
+        self.answers.get(key).copied()
+    }
+
+    pub fn record(&mut self, key: AnswerKey, answer: Answer) -> Option<Answer> {
+        self.answers.insert(key, answer)
+    }
+
+    pub fn check(&self, key: &AnswerKey, answer: Answer) -> AnswerStatus {
+        AnswerStatus::compare(answer, self.get(key))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = format!("{}\n", HEADER);
+        let mut current_day = None;
+
+        for (key, answer) in self.answers.iter() {
+            if current_day != Some(key.day) {
+                text.push_str(&format!("\n[{}]\n", day_folder_name(key.day)));
+                current_day = Some(key.day);
+            }
+
+            let input_kind = match key.is_real {
+                true => "real",
+                false => "test",
+            };
+
+            text.push_str(&format!("part_{}.{} = {}\n", key.part.number(), input_kind, answer));
+        }
+
+        text
+    }
+}
+
+// Solves the part when no answer is given. Returns the recorded answer and the one it replaced.
+pub fn record_answer(path: &Path, key: AnswerKey, answer: Option<Answer>) -> AocResult<(Answer, Option<Answer>)> {
+    let mut answer_store = AnswerStore::load(path)?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => key.solve()?,
+    };
+
+    let previous_answer = answer_store.record(key, answer);
+    answer_store.save(path)?;
+
+    Ok((answer, previous_answer))
+}
+
+// Only the default data files of a day have stored answers
+pub fn answer_key_for_source(day: &dyn Day, part: Part, source: &InputSource) -> Option<AnswerKey> {
+    match source {
+        InputSource::Data { is_real, order, .. } if *order == day.input_order(*is_real, part) => {
+            Some(AnswerKey { day: day.day(), part, is_real: *is_real })
+        },
+        _ => None,
+    }
+}
+
+pub fn answers_file_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(ANSWERS_FILE_NAME)
+}
+
+fn parse_day_section(section: &str) -> AocResult<u32> {
+    section
+        .trim()
+        .strip_prefix("dec_")
+        .and_then(|d| d.parse::<u32>().ok())
+        .ok_or(AocError::parse(format!("Expected a section like [dec_01], got [{}]", section)))
+}
+
+fn parse_answer_line(day: u32, line: &str) -> AocResult<(AnswerKey, Answer)> {
+    let Some((name, value)) = line.split_once('=') else {
+        return Err(AocError::parse("Expected a line like part_1.real = 123"));
+    };
+
+    let part = match name.trim().split_once('.') {
+        Some(("part_1", _)) => Part::One,
+        Some(("part_2", _)) => Part::Two,
+        _ => return Err(AocError::parse(format!("Unknown part in {}", name.trim()))),
+    };
+
+    let is_real = match name.trim().split_once('.') {
+        Some((_, "real")) => true,
+        Some((_, "test")) => false,
+        _ => return Err(AocError::parse(format!("Input must be test or real in {}", name.trim()))),
+    };
+
+    let answer = value
+        .trim()
+        .parse::<Answer>()
+        .map_err(|_| AocError::parse(format!("Answer must be an integer, got {}", value.trim())))?;
+
+    Ok((AnswerKey { day, part, is_real }, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const ANSWERS: &str = "\
+# Verified answers, checked by `aoc-2024 run` and updated by `aoc-2024 record`
+
+[dec_01]
+part_1.test = 11
+part_1.real = 2756096
+
+[dec_09]
+part_2.real = 6323761685944
+";
+
+    fn key(day: u32, part: Part, is_real: bool) -> AnswerKey {
+        AnswerKey { day, part, is_real }
+    }
+
+    #[test]
+    fn test_parse() {
+        let store = AnswerStore::parse(ANSWERS).unwrap();
+
+        assert_eq!(store.get(&key(1, Part::One, false)), Some(11));
+        assert_eq!(store.get(&key(1, Part::One, true)), Some(2756096));
+        assert_eq!(store.get(&key(9, Part::Two, true)), Some(6323761685944));
+        assert_eq!(store.get(&key(9, Part::One, true)), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(AnswerStore::parse(ANSWERS).unwrap().to_toml(), ANSWERS);
+    }
+
+    #[test_case(2756096, AnswerStatus::Pass)]
+    #[test_case(42, AnswerStatus::Fail { expected: 2756096 })]
+    fn test_check(answer: Answer, expected_status: AnswerStatus) {
+        let store = AnswerStore::parse(ANSWERS).unwrap();
+
+        assert_eq!(store.check(&key(1, Part::One, true), answer), expected_status);
+    }
+
+    #[test]
+    fn test_check_unknown() {
+        let store = AnswerStore::parse(ANSWERS).unwrap();
+
+        assert_eq!(store.check(&key(2, Part::One, true), 332), AnswerStatus::Unknown);
+    }
+
+    #[test]
+    fn test_record_replaces_previous_answer() {
+        let mut store = AnswerStore::parse(ANSWERS).unwrap();
+
+        assert_eq!(store.record(key(1, Part::One, false), 12), Some(11));
+        assert_eq!(store.record(key(3, Part::Two, true), 48), None);
+        assert_eq!(store.get(&key(1, Part::One, false)), Some(12));
+        assert_eq!(store.get(&key(3, Part::Two, true)), Some(48));
+    }
+
+    #[test_case("part_1.real = 5", 1)]
+    #[test_case("[day_01]", 1)]
+    #[test_case("[dec_01]\npart_3.real = 5", 2)]
+    #[test_case("[dec_01]\npart_1.fake = 5", 2)]
+    #[test_case("[dec_01]\npart_1.real = xx", 2)]
+    fn test_parse_errors(text: &str, expected_line_number: usize) {
+        let Err(AocError::Parse(parse_error)) = AnswerStore::parse(text) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!(parse_error.line_number, Some(expected_line_number));
+    }
+
+    #[test]
+    fn test_record_answer() {
+        let path = std::env::temp_dir().join(format!("aoc-2024-record-{}.toml", std::process::id()));
+        let key = AnswerKey { day: 1, part: Part::One, is_real: false };
+
+        assert_eq!(record_answer(&path, key, None).unwrap(), (11, None));
+        assert_eq!(record_answer(&path, key, Some(12)).unwrap(), (12, Some(11)));
+        assert_eq!(AnswerStore::load(&path).unwrap().get(&key), Some(12));
+        assert!(record_answer(&path, AnswerKey { day: 25, ..key }, None).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_answer_key_for_source() {
+        let day = crate::registry::find_day(3).unwrap();
+        let default_source = InputSource::Data { is_real: false, day: 3, order: Some(2) };
+        let other_source = InputSource::Data { is_real: false, day: 3, order: Some(1) };
+
+        assert_eq!(answer_key_for_source(day, Part::Two, &default_source), Some(key(3, Part::Two, false)));
+        assert_eq!(answer_key_for_source(day, Part::Two, &other_source), None);
+        assert_eq!(answer_key_for_source(day, Part::Two, &InputSource::Stdin), None);
+    }
+
+    #[test]
+    fn test_answers_file_is_valid() {
+        assert!(AnswerStore::load(&answers_file_path()).is_ok());
+    }
+}
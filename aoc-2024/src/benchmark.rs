@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant};
+
+use crate::error::AocResult;
+use crate::input::{InputCache, InputSelection};
+use crate::report::{self, Alignment};
+use crate::solution::{Answer, Day, Part};
+
+pub const DEFAULT_RUNS: u32 = 10;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug)]
+pub struct DayBenchmark {
+    pub day: u32,
+    pub parse: Summary,
+    pub parts: Vec<(Part, Answer, Summary)>,
+}
+
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+pub fn summarize(durations: &[Duration]) -> Option<Summary> {
+    let mut sorted_durations = durations.to_vec();
+    sorted_durations.sort();
+
+    let middle = sorted_durations.len() / 2;
+    let median = match sorted_durations.len() {
+        0 => return None,
+        n if n % 2 == 0 => (sorted_durations[middle - 1] + sorted_durations[middle]) / 2,
+        _ => sorted_durations[middle],
+    };
+
+    Some(Summary {
+        min: *sorted_durations.first()?,
+        median,
+        max: *sorted_durations.last()?,
+    })
+}
+
+// Parses and solves every part `runs` times, the parse timings of all parts are pooled together
+pub fn benchmark_day(
+    day: &dyn Day,
+    parts: &[Part],
+    runs: u32,
+    mut input_text: impl FnMut(Part) -> AocResult<String>,
+) -> AocResult<DayBenchmark> {
+    let mut parse_durations = Vec::new();
+    let mut part_results = Vec::new();
+
+    for part in parts {
+        let text = input_text(*part)?;
+        let mut solve_durations = Vec::new();
+        let mut answer = 0;
+
+        for _ in 0..runs.max(1) {
+            let (run_answer, timing) = day.solve_timed(*part, &text)?;
+
+            answer = run_answer;
+            parse_durations.push(timing.parse);
+            solve_durations.push(timing.solve);
+        }
+
+        part_results.push((*part, answer, summarize(&solve_durations).unwrap()));
+    }
+
+    let parse = summarize(&parse_durations).unwrap_or(Summary {
+        min: Duration::ZERO,
+        median: Duration::ZERO,
+        max: Duration::ZERO,
+    });
+
+    Ok(DayBenchmark { day: day.day(), parse, parts: part_results })
+}
+
+// Every day is benchmarked on its own, a day that fails doesn't stop the others
+pub fn benchmark_days(
+    days: &[&'static dyn Day],
+    parts: &[Part],
+    runs: u32,
+    input: &InputSelection,
+    order: Option<u32>,
+    inputs: &mut InputCache,
+) -> Vec<(u32, AocResult<DayBenchmark>)> {
+    days
+        .iter()
+        .map(|day| {
+            let benchmark = benchmark_day(*day, parts, runs, |part| {
+                Ok(inputs.read(&input.source_for(*day, part, order))?.to_string())
+            });
+
+            (day.day(), benchmark)
+        })
+        .collect()
+}
+
+pub fn format_table(benchmarks: &[DayBenchmark]) -> String {
+    let mut rows = Vec::new();
+
+    for benchmark in benchmarks {
+        rows.push(summary_row(benchmark.day, "parse".to_string(), &benchmark.parse));
+
+        for (part, _, summary) in benchmark.parts.iter() {
+            rows.push(summary_row(benchmark.day, format!("part {}", part.number()), summary));
+        }
+    }
+
+    report::format_table(
+        &["Day", "Stage", "Min", "Median", "Max"],
+        &rows,
+        &[Alignment::Left, Alignment::Left, Alignment::Right, Alignment::Right, Alignment::Right],
+    )
+}
+
+fn summary_row(day: u32, stage: String, summary: &Summary) -> Vec<String> {
+    vec![
+        day.to_string(),
+        stage,
+        format_duration(summary.min),
+        format_duration(summary.median),
+        format_duration(summary.max),
+    ]
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn microseconds(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_micros(*v)).collect()
+    }
+
+    #[test_case(&[5], 5, 5, 5)]
+    #[test_case(&[3, 1, 2], 1, 2, 3)]
+    #[test_case(&[40, 10, 30, 20], 10, 25, 40)]
+    fn test_summarize(values: &[u64], expected_min: u64, expected_median: u64, expected_max: u64) {
+        let expected_summary = Summary {
+            min: Duration::from_micros(expected_min),
+            median: Duration::from_micros(expected_median),
+            max: Duration::from_micros(expected_max),
+        };
+
+        assert_eq!(summarize(&microseconds(values)), Some(expected_summary));
+    }
+
+    #[test]
+    fn test_summarize_nothing() {
+        assert_eq!(summarize(&[]), None);
+    }
+
+    #[test]
+    fn test_benchmark_day_collects_every_part() {
+        let day = crate::registry::find_day(1).unwrap();
+        let text = crate::util::read_single_string_from_file(false, 1, None).unwrap();
+
+        let benchmark = benchmark_day(day, &[Part::One, Part::Two], 3, |_| Ok(text.clone())).unwrap();
+
+        assert_eq!(benchmark.day, 1);
+        assert_eq!(benchmark.parts.iter().map(|(p, a, _)| (*p, *a)).collect::<Vec<_>>(), vec![(Part::One, 11), (Part::Two, 31)]);
+        assert!(benchmark.parse.min <= benchmark.parse.median && benchmark.parse.median <= benchmark.parse.max);
+    }
+
+    #[test]
+    fn test_benchmark_days_keeps_failures_apart() {
+        let days = [crate::registry::find_day(1).unwrap(), crate::registry::find_day(3).unwrap()];
+        let input = InputSelection::File(std::env::temp_dir().join("aoc-2024-no-benchmark-input.txt"));
+
+        let benchmarks = benchmark_days(&days, &[Part::One], 1, &InputSelection::Test, None, &mut InputCache::new());
+
+        assert_eq!(benchmarks.iter().map(|(d, b)| (*d, b.is_ok())).collect::<Vec<_>>(), vec![(1, true), (3, true)]);
+        assert!(benchmark_days(&days[..1], &[Part::One], 1, &input, None, &mut InputCache::new())[0].1.is_err());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let summary = Summary {
+            min: Duration::from_micros(1500),
+            median: Duration::from_millis(2),
+            max: Duration::from_millis(10),
+        };
+        let benchmark = DayBenchmark { day: 4, parse: summary, parts: vec![(Part::Two, 9, summary)] };
+
+        let expected_table = "\
+Day  Stage      Min  Median      Max
+4    parse   1.50ms  2.00ms  10.00ms
+4    part 2  1.50ms  2.00ms  10.00ms";
+
+        assert_eq!(format_table(&[benchmark]), expected_table);
+    }
+}
@@ -0,0 +1,535 @@
+use std::path::PathBuf;
+
+use aoc_2024::benchmark::DEFAULT_RUNS;
+use aoc_2024::report::OutputFormat;
+use aoc_2024::solution::{parts, Answer, Part};
+use aoc_2024::util::render::ImageFormat;
+
+pub use aoc_2024::input::InputSelection;
+pub use aoc_2024::registry::DaySelection;
+
+pub const USAGE: &str = "\
+Usage:
+    aoc-2024 run [--day <N> | --all] [--part <1|2>] [--input <test|real>] [--order <N>] [--input-file <PATH>] [--parallel] [--format <text|json|csv>]
+    aoc-2024 bench [--runs <N>] [same options as run]
+    aoc-2024 record --day <N> --part <1|2> [--input <test|real>] [--answer <VALUE>]
+    aoc-2024 new-day <N>
+    aoc-2024 import --day <N> --page <PATH>
+    aoc-2024 fetch --day <N>
+    aoc-2024 submit --day <N> --part <1|2> [--answer <VALUE>]
+    aoc-2024 render --day <N> [--input <test|real>] [--output <PATH>] [--scale <N>]
+
+Options:
+    --day <N>             Run a single day (defaults to the latest solved day)
+    --all                 Run every registered day
+    --part <1|2>          Run a single part (defaults to both)
+    --input <test|real>   Choose between test_data.txt and real_data.txt (defaults to real)
+    --order <N>           Read test_data_N.txt / real_data_N.txt instead
+    --input-file <PATH>   Read the input from an arbitrary file, or stdin when PATH is -, requires --day
+    --parallel            Solve all selected parts on a thread pool and print a summary table
+    --format <FORMAT>     Print the results as a text table, JSON or CSV (defaults to text)
+    --runs <N>            How many times bench parses and solves each part (defaults to 10)
+    --answer <VALUE>      The answer to record or submit, solves the part when left out
+    --page <PATH>         A puzzle page saved from the browser, its examples and example answers are imported
+    --output <PATH>       Save the rendering as a .png or .ppm image instead of printing it
+    --scale <N>           Width of a cell in pixels in saved images (defaults to 4)
+    --help                Print this message
+
+fetch and submit use the session cookie in AOC_SESSION, AOC_BASE_URL points them at another server
+render draws the cheapest route through the maze of day 16";
+
+pub const DEFAULT_SCALE: usize = 4;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Record(RecordOptions),
+    NewDay(u32),
+    Import(ImportOptions),
+    Fetch(u32),
+    Submit(SubmitOptions),
+    Render(RenderOptions),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub days: DaySelection,
+    pub parts: Vec<Part>,
+    pub input: InputSelection,
+    pub order: Option<u32>,
+    pub parallel: bool,
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub run_options: RunOptions,
+    pub runs: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RecordOptions {
+    pub day: u32,
+    pub part: Part,
+    pub is_real: bool,
+    pub answer: Option<Answer>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ImportOptions {
+    pub day: u32,
+    pub page: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SubmitOptions {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Option<Answer>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RenderOptions {
+    pub day: u32,
+    pub is_real: bool,
+    pub output: Option<(PathBuf, ImageFormat)>,
+    pub scale: usize,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            days: DaySelection::Latest,
+            parts: parts(),
+            input: InputSelection::Real,
+            order: None,
+            parallel: false,
+            format: OutputFormat::Text,
+        }
+    }
+}
+
+pub fn parse_arguments(arguments: &[String]) -> Result<Command, String> {
+    let mut argument_iterator = arguments.iter();
+
+    match argument_iterator.next().map(|a| a.as_str()) {
+        None => Ok(Command::Run(RunOptions::default())),
+        Some("run") => parse_run_options(argument_iterator),
+        Some("bench") => parse_bench_options(argument_iterator),
+        Some("record") => parse_record_options(argument_iterator),
+        Some("new-day") => match (argument_iterator.next(), argument_iterator.next()) {
+            (Some(day), None) => Ok(Command::NewDay(parse_number(day, "new-day")?)),
+            _ => Err("new-day expects a single day number".to_string()),
+        },
+        Some("import") => parse_import_options(argument_iterator),
+        Some("fetch") => parse_fetch_options(argument_iterator),
+        Some("submit") => parse_submit_options(argument_iterator),
+        Some("render") => parse_render_options(argument_iterator),
+        Some("help" | "--help" | "-h") => Ok(Command::Help),
+        Some(other) => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+fn parse_run_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut options = RunOptions::default();
+    let mut input_kind = None;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => {
+                let day = parse_number(next_value(&mut argument_iterator, argument)?, argument)?;
+                options.days = DaySelection::Single(day);
+            },
+            "--all" => options.days = DaySelection::All,
+            "--part" => options.parts = vec![parse_part(next_value(&mut argument_iterator, argument)?)?],
+            "--input" => input_kind = Some(parse_input_kind(next_value(&mut argument_iterator, argument)?)?),
+            "--order" => {
+                let order = parse_number(next_value(&mut argument_iterator, argument)?, argument)?;
+                options.order = Some(order);
+            },
+            "--input-file" => {
+                options.input = match next_value(&mut argument_iterator, argument)? {
+                    "-" => InputSelection::Stdin,
+                    path => InputSelection::File(PathBuf::from(path)),
+                };
+            },
+            "--parallel" => options.parallel = true,
+            "--format" => {
+                let value = next_value(&mut argument_iterator, argument)?;
+                options.format = OutputFormat::from_name(value)
+                    .ok_or(format!("Format must be text, json or csv, got {}", value))?;
+            },
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    let reads_from_file = matches!(options.input, InputSelection::File(_) | InputSelection::Stdin);
+
+    match input_kind {
+        Some(_) if reads_from_file => return Err("--input and --input-file cannot be combined".to_string()),
+        Some(kind) => options.input = kind,
+        None => (),
+    }
+
+    if reads_from_file {
+        if !matches!(options.days, DaySelection::Single(_)) {
+            return Err("--input-file needs a single --day".to_string());
+        }
+
+        if options.order.is_some() {
+            return Err("--order cannot be combined with --input-file".to_string());
+        }
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn parse_bench_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut runs = DEFAULT_RUNS;
+    let mut run_arguments = Vec::new();
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--runs" => {
+                runs = parse_number(next_value(&mut argument_iterator, argument)?, argument)?;
+
+                if runs == 0 {
+                    return Err("--runs must be at least 1".to_string());
+                }
+            },
+            _ => run_arguments.push(argument),
+        }
+    }
+
+    match parse_run_options(run_arguments.into_iter())? {
+        Command::Run(run_options) => Ok(Command::Bench(BenchOptions { run_options, runs })),
+        command => Ok(command),
+    }
+}
+
+fn parse_record_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input_kind = InputSelection::Real;
+    let mut answer = None;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => day = Some(parse_number(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--part" => part = Some(parse_part(next_value(&mut argument_iterator, argument)?)?),
+            "--input" => input_kind = parse_input_kind(next_value(&mut argument_iterator, argument)?)?,
+            "--answer" => answer = Some(parse_answer(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    Ok(Command::Record(RecordOptions {
+        day: day.ok_or("record needs a --day".to_string())?,
+        part: part.ok_or("record needs a --part".to_string())?,
+        is_real: input_kind == InputSelection::Real,
+        answer,
+    }))
+}
+
+fn parse_import_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut page = None;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => day = Some(parse_number(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--page" => page = Some(PathBuf::from(next_value(&mut argument_iterator, argument)?)),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    Ok(Command::Import(ImportOptions {
+        day: day.ok_or("import needs a --day".to_string())?,
+        page: page.ok_or("import needs a --page".to_string())?,
+    }))
+}
+
+fn parse_fetch_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut day = None;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => day = Some(parse_number(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    Ok(Command::Fetch(day.ok_or("fetch needs a --day".to_string())?))
+}
+
+fn parse_submit_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut answer = None;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => day = Some(parse_number(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--part" => part = Some(parse_part(next_value(&mut argument_iterator, argument)?)?),
+            "--answer" => answer = Some(parse_answer(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    Ok(Command::Submit(SubmitOptions {
+        day: day.ok_or("submit needs a --day".to_string())?,
+        part: part.ok_or("submit needs a --part".to_string())?,
+        answer,
+    }))
+}
+
+fn parse_render_options<'a>(mut argument_iterator: impl Iterator<Item = &'a String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut input_kind = InputSelection::Real;
+    let mut output = None;
+    let mut scale = DEFAULT_SCALE;
+
+    while let Some(argument) = argument_iterator.next() {
+        match argument.as_str() {
+            "--day" => day = Some(parse_number(next_value(&mut argument_iterator, argument)?, argument)?),
+            "--input" => input_kind = parse_input_kind(next_value(&mut argument_iterator, argument)?)?,
+            "--output" => {
+                let path = PathBuf::from(next_value(&mut argument_iterator, argument)?);
+                let format = ImageFormat::from_path(&path)
+                    .ok_or(format!("--output must end in .png or .ppm, got {}", path.display()))?;
+
+                output = Some((path, format));
+            },
+            "--scale" => {
+                scale = parse_number(next_value(&mut argument_iterator, argument)?, argument)? as usize;
+
+                if scale == 0 {
+                    return Err("--scale must be at least 1".to_string());
+                }
+            },
+            "--help" | "-h" => return Ok(Command::Help),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    Ok(Command::Render(RenderOptions {
+        day: day.ok_or("render needs a --day".to_string())?,
+        is_real: input_kind == InputSelection::Real,
+        output,
+        scale,
+    }))
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        other => Err(format!("Part must be 1 or 2, got {}", other)),
+    }
+}
+
+fn parse_input_kind(value: &str) -> Result<InputSelection, String> {
+    match value {
+        "test" => Ok(InputSelection::Test),
+        "real" => Ok(InputSelection::Real),
+        other => Err(format!("Input must be test or real, got {}", other)),
+    }
+}
+
+fn next_value<'a>(argument_iterator: &mut impl Iterator<Item = &'a String>, option: &str) -> Result<&'a str, String> {
+    argument_iterator
+        .next()
+        .map(|v| v.as_str())
+        .ok_or(format!("Missing value for {}", option))
+}
+
+fn parse_answer(value: &str, option: &str) -> Result<Answer, String> {
+    value
+        .parse::<Answer>()
+        .map_err(|_| format!("{} expects an integer, got {}", option, value))
+}
+
+fn parse_number(value: &str, option: &str) -> Result<u32, String> {
+    value
+        .parse::<u32>()
+        .map_err(|_| format!("{} expects a positive number, got {}", option, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn to_arguments(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_arguments_runs_latest_day() {
+        assert_eq!(parse_arguments(&[]), Ok(Command::Run(RunOptions::default())));
+    }
+
+    #[test]
+    fn test_parse_single_day_and_part() {
+        let expected_options = RunOptions {
+            days: DaySelection::Single(7),
+            parts: vec![Part::Two],
+            input: InputSelection::Test,
+            order: None,
+            parallel: false,
+            format: OutputFormat::Text,
+        };
+
+        assert_eq!(parse_arguments(&to_arguments("run --day 7 --part 2 --input test")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_all_days() {
+        let expected_options = RunOptions { days: DaySelection::All, ..RunOptions::default() };
+
+        assert_eq!(parse_arguments(&to_arguments("run --all")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_parallel() {
+        let expected_options = RunOptions { days: DaySelection::All, parallel: true, ..RunOptions::default() };
+
+        assert_eq!(parse_arguments(&to_arguments("run --all --parallel")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        let expected_options = RunOptions { format: OutputFormat::Json, ..RunOptions::default() };
+
+        assert_eq!(parse_arguments(&to_arguments("run --format json")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_input_file() {
+        let expected_options = RunOptions {
+            days: DaySelection::Single(3),
+            input: InputSelection::File(PathBuf::from("/tmp/dec_03.txt")),
+            ..RunOptions::default()
+        };
+
+        assert_eq!(parse_arguments(&to_arguments("run --input-file /tmp/dec_03.txt --day 3")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_stdin() {
+        let expected_options = RunOptions {
+            days: DaySelection::Single(11),
+            input: InputSelection::Stdin,
+            ..RunOptions::default()
+        };
+
+        assert_eq!(parse_arguments(&to_arguments("run --day 11 --input-file -")), Ok(Command::Run(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        let expected_options = BenchOptions {
+            run_options: RunOptions { days: DaySelection::All, ..RunOptions::default() },
+            runs: 25,
+        };
+
+        assert_eq!(parse_arguments(&to_arguments("bench --all --runs 25")), Ok(Command::Bench(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_bench_default_runs() {
+        let expected_options = BenchOptions { run_options: RunOptions::default(), runs: DEFAULT_RUNS };
+
+        assert_eq!(parse_arguments(&to_arguments("bench")), Ok(Command::Bench(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let expected_options = RecordOptions { day: 9, part: Part::Two, is_real: false, answer: Some(2858) };
+
+        assert_eq!(parse_arguments(&to_arguments("record --day 9 --part 2 --input test --answer 2858")), Ok(Command::Record(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_record_without_answer() {
+        let expected_options = RecordOptions { day: 1, part: Part::One, is_real: true, answer: None };
+
+        assert_eq!(parse_arguments(&to_arguments("record --part 1 --day 1")), Ok(Command::Record(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_new_day() {
+        assert_eq!(parse_arguments(&to_arguments("new-day 17")), Ok(Command::NewDay(17)));
+    }
+
+    #[test]
+    fn test_parse_import() {
+        let expected_options = ImportOptions { day: 3, page: PathBuf::from("/tmp/day3.html") };
+
+        assert_eq!(parse_arguments(&to_arguments("import --page /tmp/day3.html --day 3")), Ok(Command::Import(expected_options)));
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(parse_arguments(&to_arguments("fetch --day 17")), Ok(Command::Fetch(17)));
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        let expected_options = SubmitOptions { day: 5, part: Part::One, answer: Some(143) };
+
+        assert_eq!(parse_arguments(&to_arguments("submit --day 5 --part 1 --answer 143")), Ok(Command::Submit(expected_options)));
+    }
+
+    #[test_case("render --day 16", RenderOptions { day: 16, is_real: true, output: None, scale: DEFAULT_SCALE })]
+    #[test_case(
+        "render --day 16 --input test --output /tmp/maze.png --scale 8",
+        RenderOptions { day: 16, is_real: false, output: Some((PathBuf::from("/tmp/maze.png"), ImageFormat::Png)), scale: 8 }
+    )]
+    fn test_parse_render(line: &str, expected_options: RenderOptions) {
+        assert_eq!(parse_arguments(&to_arguments(line)), Ok(Command::Render(expected_options)));
+    }
+
+    #[test_case("run --day")]
+    #[test_case("run --day seven")]
+    #[test_case("run --part 3")]
+    #[test_case("run --input fake")]
+    #[test_case("run --all --input-file input.txt")]
+    #[test_case("run --input-file x.txt")]
+    #[test_case("run --input-file -")]
+    #[test_case("run -")]
+    #[test_case("run --day 1 --input test --input-file input.txt")]
+    #[test_case("run --verbose")]
+    #[test_case("run --runs 5")]
+    #[test_case("run --format xml")]
+    #[test_case("bench --runs 0")]
+    #[test_case("bench --runs")]
+    #[test_case("record --day 1")]
+    #[test_case("record --part 1")]
+    #[test_case("record --day 1 --part 1 --answer xx")]
+    #[test_case("record --day 1 --part 1 --all")]
+    #[test_case("new-day")]
+    #[test_case("new-day seventeen")]
+    #[test_case("new-day 17 18")]
+    #[test_case("import --day 3")]
+    #[test_case("import --page day3.html")]
+    #[test_case("import --day 3 --page day3.html --part 1")]
+    #[test_case("fetch")]
+    #[test_case("fetch --day 1 --part 1")]
+    #[test_case("submit --day 1")]
+    #[test_case("submit --day 1 --part 2 --answer high")]
+    #[test_case("render")]
+    #[test_case("render --day 16 --output maze.jpg")]
+    #[test_case("render --day 16 --scale 0")]
+    #[test_case("walk")]
+    fn test_invalid_arguments(line: &str) {
+        assert!(parse_arguments(&to_arguments(line)).is_err());
+    }
+}
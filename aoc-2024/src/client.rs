@@ -0,0 +1,720 @@
+use std::fmt;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::answers::{AnswerKey, AnswerStore};
+use crate::error::{AocError, AocResult};
+use crate::input::{data_directories, data_file_name, day_folder_name, InputError};
+use crate::puzzle::decode_html;
+use crate::solution::{Answer, Part};
+use crate::util::parsing::LazyRegex;
+
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2024;
+
+// The site asks automated tools to keep their request rate down
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/gudjont89/aoc-2024";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect { hint: Option<AnswerHint>, wait: Option<Duration> },
+    // Answers were submitted too recently, nothing was checked
+    RateLimited { wait: Option<Duration> },
+    // The part was solved before or is not unlocked yet
+    WrongLevel,
+    Unrecognised(String),
+}
+
+impl fmt::Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer"),
+            SubmissionOutcome::Incorrect { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+
+                match hint {
+                    Some(AnswerHint::TooHigh) => write!(f, ", it is too high")?,
+                    Some(AnswerHint::TooLow) => write!(f, ", it is too low")?,
+                    None => (),
+                }
+
+                match wait {
+                    Some(wait) => write!(f, ", wait {}s before trying again", wait.as_secs()),
+                    None => Ok(()),
+                }
+            },
+            SubmissionOutcome::RateLimited { wait: Some(wait) } => write!(f, "Submitted too recently, wait {}s", wait.as_secs()),
+            SubmissionOutcome::RateLimited { wait: None } => write!(f, "Submitted too recently"),
+            SubmissionOutcome::WrongLevel => write!(f, "This part is already solved or not unlocked yet"),
+            SubmissionOutcome::Unrecognised(text) => write!(f, "Unrecognised response: {}", text),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct BaseUrl {
+    is_secure: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl BaseUrl {
+    fn parse(url: &str) -> AocResult<Self> {
+        let (is_secure, rest) = match (url.strip_prefix("https://"), url.strip_prefix("http://")) {
+            (Some(rest), _) => (true, rest),
+            (_, Some(rest)) => (false, rest),
+            _ => return Err(AocError::http(format!("Expected an http:// or https:// URL, got {}", url))),
+        };
+
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+        let (host, port) = match authority.split_once(':') {
+            Some((host, port)) => {
+                let port = port.parse::<u16>().map_err(|_| AocError::http(format!("Invalid port in {}", url)))?;
+                (host, port)
+            },
+            None if is_secure => (authority, 443),
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(AocError::http(format!("Missing host in {}", url)));
+        }
+
+        let path = path.trim_end_matches('/');
+        let path = match path.is_empty() {
+            true => String::new(),
+            false => format!("/{}", path),
+        };
+
+        Ok(BaseUrl { is_secure, host: host.to_string(), port, path })
+    }
+
+    fn url(&self, path: &str) -> String {
+        let scheme = match self.is_secure {
+            true => "https",
+            false => "http",
+        };
+
+        format!("{}://{}:{}{}{}", scheme, self.host, self.port, self.path, path)
+    }
+}
+
+#[derive(Debug)]
+struct HttpResponse {
+    status: u16,
+    body: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Submission {
+    AlreadyRecorded(Answer),
+    Submitted { answer: Answer, outcome: SubmissionOutcome },
+}
+
+// Downloads inputs and submits answers, waiting between requests so the server isn't hammered
+pub struct AocClient {
+    base_url: BaseUrl,
+    session: String,
+    cache_directory: PathBuf,
+    request_interval: Duration,
+    last_request: Option<SystemTime>,
+}
+
+// Holds the time of the last request in milliseconds since the Unix epoch, so separate runs wait for each other too
+const LAST_REQUEST_FILE_NAME: &str = ".last_request";
+
+impl AocClient {
+    // Downloaded inputs are stored as <cache_directory>/dec_NN/real_data.txt, the same layout as the data folders
+    pub fn new(base_url: &str, session: &str, cache_directory: &Path) -> AocResult<Self> {
+        let session = session.trim();
+
+        // The token ends up in a header, so anything beyond the hex characters the site hands out is refused
+        if session.is_empty() || !session.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(AocError::http("The session token should only contain letters and digits"));
+        }
+
+        Ok(AocClient {
+            base_url: BaseUrl::parse(base_url)?,
+            session: session.to_string(),
+            cache_directory: cache_directory.to_path_buf(),
+            request_interval: DEFAULT_REQUEST_INTERVAL,
+            last_request: None,
+        })
+    }
+
+    // Needs AOC_SESSION, AOC_BASE_URL defaults to the real site and inputs go to the first data directory
+    pub fn from_environment() -> AocResult<Self> {
+        let session = std::env::var(SESSION_VARIABLE)
+            .map_err(|_| AocError::http(format!("Set {} to the session cookie of the site", SESSION_VARIABLE)))?;
+        let base_url = std::env::var(BASE_URL_VARIABLE).unwrap_or(DEFAULT_BASE_URL.to_string());
+
+        AocClient::new(&base_url, &session, &data_directories()[0])
+    }
+
+    pub fn with_request_interval(self, request_interval: Duration) -> Self {
+        AocClient { request_interval, ..self }
+    }
+
+    pub fn cached_input_path(&self, day: u32) -> PathBuf {
+        self.cache_directory.join(day_folder_name(day)).join(data_file_name(true, None))
+    }
+
+    // Inputs never change, so each one is downloaded at most once
+    pub fn fetch_input(&mut self, day: u32) -> AocResult<String> {
+        let path = self.cached_input_path(day);
+
+        if let Ok(text) = std::fs::read_to_string(&path) {
+            if !text.trim().is_empty() {
+                return Ok(text);
+            }
+        }
+
+        let response = self.send(&format!("/{}/day/{}/input", YEAR, day), None)?;
+
+        let text = match response.status {
+            200 => response.body,
+            404 => return Err(AocError::http(format!("The input of day {} is not available yet", day))),
+            400 | 500 => return Err(AocError::http("The session token was not accepted")),
+            status => return Err(AocError::http(format!("Unexpected status {} when fetching day {}", status, day))),
+        };
+
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)
+                .map_err(|error| InputError::Unwritable { path: directory.to_path_buf(), error })?;
+        }
+
+        std::fs::write(&path, &text).map_err(|error| InputError::Unwritable { path: path.clone(), error })?;
+
+        Ok(text)
+    }
+
+    pub fn submit_answer(&mut self, day: u32, part: Part, answer: Answer) -> AocResult<SubmissionOutcome> {
+        let form = format!("level={}&answer={}", part.number(), answer);
+        let response = self.send(&format!("/{}/day/{}/answer", YEAR, day), Some(&form))?;
+
+        match response.status {
+            200 => Ok(parse_submission_response(&response.body)),
+            400 | 500 => Err(AocError::http("The session token was not accepted")),
+            status => Err(AocError::http(format!("Unexpected status {} when submitting day {}", status, day))),
+        }
+    }
+
+    // Posts the form when there is one, otherwise gets the path
+    fn send(&mut self, path: &str, form: Option<&str>) -> AocResult<HttpResponse> {
+        let last_request = [self.last_request, self.read_last_request()].into_iter().flatten().max();
+
+        if let Some(last_request) = last_request {
+            // A last request in the future means the clock was turned back, so the whole interval is waited
+            let elapsed = SystemTime::now().duration_since(last_request).unwrap_or(Duration::ZERO);
+
+            if elapsed < self.request_interval {
+                std::thread::sleep(self.request_interval - elapsed);
+            }
+        }
+
+        let now = SystemTime::now();
+        self.last_request = Some(now);
+        self.write_last_request(now)?;
+
+        match self.base_url.is_secure {
+            true => send_with_curl(&self.base_url.url(path), &self.session, form),
+            false => send_over_tcp(&self.base_url, path, &self.session, form),
+        }
+    }
+
+    fn last_request_path(&self) -> PathBuf {
+        self.cache_directory.join(LAST_REQUEST_FILE_NAME)
+    }
+
+    fn read_last_request(&self) -> Option<SystemTime> {
+        let text = std::fs::read_to_string(self.last_request_path()).ok()?;
+        let milliseconds = text.trim().parse::<u64>().ok()?;
+
+        UNIX_EPOCH.checked_add(Duration::from_millis(milliseconds))
+    }
+
+    fn write_last_request(&self, time: SystemTime) -> AocResult<()> {
+        // Rounded up, so the next request never comes early
+        let milliseconds = time.duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO).as_nanos().div_ceil(1_000_000);
+
+        std::fs::create_dir_all(&self.cache_directory)
+            .map_err(|error| InputError::Unwritable { path: self.cache_directory.clone(), error })?;
+
+        let path = self.last_request_path();
+        std::fs::write(&path, milliseconds.to_string()).map_err(|error| InputError::Unwritable { path, error })?;
+
+        Ok(())
+    }
+}
+
+// A correct answer is recorded right away, and parts with a recorded answer are never submitted again.
+// The part is solved when no answer is given, and the client is only connected when something is sent.
+pub fn submit_and_record(
+    answers_path: &Path,
+    day: u32,
+    part: Part,
+    answer: Option<Answer>,
+    connect: impl FnOnce() -> AocResult<AocClient>,
+) -> AocResult<Submission> {
+    let mut answer_store = AnswerStore::load(answers_path)?;
+    let key = AnswerKey { day, part, is_real: true };
+
+    if let Some(recorded_answer) = answer_store.get(&key) {
+        return Ok(Submission::AlreadyRecorded(recorded_answer));
+    }
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => key.solve()?,
+    };
+
+    let outcome = connect()?.submit_answer(day, part, answer)?;
+
+    if outcome == SubmissionOutcome::Correct {
+        answer_store.record(key, answer);
+        answer_store.save(answers_path)?;
+    }
+
+    Ok(Submission::Submitted { answer, outcome })
+}
+
+pub fn parse_submission_response(html: &str) -> SubmissionOutcome {
+    static ARTICLE: LazyRegex = LazyRegex::new(r"(?s)<article[^>]*>(.*?)</article>");
+
+    let text = ARTICLE
+        .captures(html)
+        .map(|c| decode_html(&c[1]))
+        .unwrap_or(decode_html(html));
+    let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        return SubmissionOutcome::Correct;
+    }
+
+    if text.contains("That's not the right answer") {
+        let hint = match (text.contains("too high"), text.contains("too low")) {
+            (true, false) => Some(AnswerHint::TooHigh),
+            (false, true) => Some(AnswerHint::TooLow),
+            _ => None,
+        };
+
+        return SubmissionOutcome::Incorrect { hint, wait: parse_wait(&text) };
+    }
+
+    if text.contains("You gave an answer too recently") {
+        return SubmissionOutcome::RateLimited { wait: parse_wait(&text) };
+    }
+
+    if text.contains("You don't seem to be solving the right level") {
+        return SubmissionOutcome::WrongLevel;
+    }
+
+    SubmissionOutcome::Unrecognised(text)
+}
+
+// The site either says "You have 1m 5s left to wait" or "please wait 5 minutes before trying again"
+fn parse_wait(text: &str) -> Option<Duration> {
+    static TIME_LEFT: LazyRegex = LazyRegex::new(r"You have (?:(\d+)m )?(\d+)s left to wait");
+    static MINUTES: LazyRegex = LazyRegex::new(r"wait (one|\d+) minutes? before trying again");
+
+    if let Some(captures) = TIME_LEFT.captures(text) {
+        let minutes = captures.get(1).and_then(|m| m.as_str().parse::<u64>().ok()).unwrap_or(0);
+        let seconds = captures[2].parse::<u64>().ok()?;
+
+        return Some(Duration::from_secs(60 * minutes + seconds));
+    }
+
+    let minutes = match &MINUTES.captures(text)?[1] {
+        "one" => 1,
+        minutes => minutes.parse::<u64>().ok()?,
+    };
+
+    Some(Duration::from_secs(60 * minutes))
+}
+
+// Plain HTTP with the standard library, used for local servers
+fn send_over_tcp(base_url: &BaseUrl, path: &str, session: &str, form: Option<&str>) -> AocResult<HttpResponse> {
+    let address = format!("{}:{}", base_url.host, base_url.port);
+    let connection_error = |error: std::io::Error| AocError::http(format!("{}: {}", address, error));
+
+    let mut stream = TcpStream::connect(&address).map_err(connection_error)?;
+    stream.set_read_timeout(Some(TIMEOUT)).map_err(connection_error)?;
+
+    let method = match form {
+        Some(_) => "POST",
+        None => "GET",
+    };
+
+    let mut request = format!(
+        "{} {}{} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n",
+        method,
+        base_url.path,
+        path,
+        base_url.host,
+        USER_AGENT,
+        session,
+    );
+
+    match form {
+        Some(form) => request.push_str(&format!(
+            "Content-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{}",
+            form.len(),
+            form,
+        )),
+        None => request.push_str("\r\n"),
+    }
+
+    stream.write_all(request.as_bytes()).map_err(connection_error)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(connection_error)?;
+
+    parse_http_response(&String::from_utf8_lossy(&response))
+}
+
+fn parse_http_response(response: &str) -> AocResult<HttpResponse> {
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or(AocError::http("The response has no end of headers"))?;
+
+    let mut head_lines = head.lines();
+
+    let status = head_lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or(AocError::http("The response has no status"))?;
+
+    let is_chunked = head_lines.any(|l| {
+        l.split_once(':')
+            .is_some_and(|(name, value)| name.trim().eq_ignore_ascii_case("transfer-encoding") && value.contains("chunked"))
+    });
+
+    let body = match is_chunked {
+        true => decode_chunked_body(body)?,
+        false => body.to_string(),
+    };
+
+    Ok(HttpResponse { status, body })
+}
+
+fn decode_chunked_body(mut body: &str) -> AocResult<String> {
+    let mut decoded = String::new();
+
+    loop {
+        let (size, rest) = body.split_once("\r\n").ok_or(AocError::http("Incomplete chunked response"))?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| AocError::http(format!("Invalid chunk size {}", size)))?;
+
+        if size == 0 {
+            return Ok(decoded);
+        }
+
+        let chunk = rest.get(..size).ok_or(AocError::http("Incomplete chunked response"))?;
+        decoded.push_str(chunk);
+        body = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+// The standard library has no TLS, so secure requests go through curl. The cookie is
+// passed as a config file on stdin, which keeps it out of the process list.
+fn send_with_curl(url: &str, session: &str, form: Option<&str>) -> AocResult<HttpResponse> {
+    let mut config = format!("header = \"Cookie: session={}\"\nuser-agent = \"{}\"\n", session, USER_AGENT);
+
+    if let Some(form) = form {
+        config.push_str(&format!("data = \"{}\"\n", form));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--config", "-", "--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| AocError::http(format!("Could not start curl, which is needed for https: {}", error)))?;
+
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(config.as_bytes())
+        .map_err(|error| AocError::http(format!("Could not pass the request to curl: {}", error)))?;
+
+    let output = child
+        .wait_with_output()
+        .map_err(|error| AocError::http(format!("curl did not finish: {}", error)))?;
+
+    if !output.status.success() {
+        return Err(AocError::http(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').ok_or(AocError::http("curl gave no status"))?;
+
+    let status = status
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| AocError::http(format!("curl gave an invalid status {}", status)))?;
+
+    Ok(HttpResponse { status, body: body.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread::JoinHandle;
+    use std::time::Instant;
+    use test_case::test_case;
+
+    // Answers every request from the given (path, status, body) routes and keeps the requests it saw
+    struct MockServer {
+        base_url: String,
+        requests: Arc<Mutex<Vec<String>>>,
+        handle: JoinHandle<()>,
+    }
+
+    impl MockServer {
+        fn start(routes: Vec<(&'static str, u16, &'static str)>, number_of_requests: usize) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}/", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let seen_requests = Arc::clone(&requests);
+
+            let handle = std::thread::spawn(move || {
+                for stream in listener.incoming().take(number_of_requests) {
+                    let mut stream = stream.unwrap();
+                    let request = read_request(&mut stream);
+
+                    let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+                    let (status, body) = routes
+                        .iter()
+                        .find(|(p, _, _)| *p == path)
+                        .map(|(_, status, body)| (*status, *body))
+                        .unwrap_or((404, "Not found"));
+
+                    let response = format!("HTTP/1.1 {} Mock\r\nContent-Length: {}\r\n\r\n{}", status, body.len(), body);
+                    stream.write_all(response.as_bytes()).unwrap();
+
+                    seen_requests.lock().unwrap().push(request);
+                }
+            });
+
+            MockServer { base_url, requests, handle }
+        }
+
+        fn finish(self) -> Vec<String> {
+            self.handle.join().unwrap();
+
+            Arc::try_unwrap(self.requests).unwrap().into_inner().unwrap()
+        }
+    }
+
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+
+        loop {
+            let count = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..count]);
+
+            let text = String::from_utf8_lossy(&request).to_string();
+
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let content_length = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Content-Length: "))
+                    .and_then(|l| l.parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                if body.len() >= content_length {
+                    return text;
+                }
+            }
+
+            if count == 0 {
+                return text;
+            }
+        }
+    }
+
+    fn cache_directory(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-2024-client-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_fetch_input_is_cached() {
+        let server = MockServer::start(vec![("/2024/day/5/input", 200, "47|53\n")], 1);
+        let directory = cache_directory("fetch");
+        let mut client = AocClient::new(&server.base_url, "abc123", &directory).unwrap().with_request_interval(Duration::ZERO);
+
+        assert_eq!(client.fetch_input(5).unwrap(), "47|53\n");
+        assert_eq!(client.fetch_input(5).unwrap(), "47|53\n");
+        assert_eq!(std::fs::read_to_string(directory.join("dec_05").join("real_data.txt")).unwrap(), "47|53\n");
+
+        let requests = server.finish();
+
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2024/day/5/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=abc123\r\n"));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_unavailable_input() {
+        let server = MockServer::start(vec![], 1);
+        let directory = cache_directory("missing");
+        let mut client = AocClient::new(&server.base_url, "abc123", &directory).unwrap();
+
+        assert!(matches!(client.fetch_input(25), Err(AocError::Http(_))));
+        assert!(!client.cached_input_path(25).exists());
+
+        server.finish();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_submit_answer() {
+        let body = "<main><article><p>That's not the right answer; your answer is too low. Please wait one minute before trying again.</p></article></main>";
+        let server = MockServer::start(vec![("/2024/day/7/answer", 200, body)], 1);
+        let directory = cache_directory("submit");
+        let mut client = AocClient::new(&server.base_url, "abc123", &directory).unwrap();
+
+        let outcome = client.submit_answer(7, Part::Two, 3749).unwrap();
+
+        assert_eq!(outcome, SubmissionOutcome::Incorrect { hint: Some(AnswerHint::TooLow), wait: Some(Duration::from_secs(60)) });
+
+        let requests = server.finish();
+
+        assert!(requests[0].starts_with("POST /2024/day/7/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=3749"));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_correct_answers_are_recorded_and_not_submitted_again() {
+        let body = "<main><article><p>That's the right answer!  You are one gold star closer.</p></article></main>";
+        let server = MockServer::start(vec![("/2024/day/7/answer", 200, body)], 1);
+        let directory = cache_directory("record");
+        let answers_path = directory.join("answers.toml");
+        let connect = || AocClient::new(&server.base_url, "abc123", &directory);
+
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let submission = submit_and_record(&answers_path, 7, Part::One, Some(3749), connect).unwrap();
+
+        assert_eq!(submission, Submission::Submitted { answer: 3749, outcome: SubmissionOutcome::Correct });
+        assert!(std::fs::read_to_string(&answers_path).unwrap().contains("[dec_07]\npart_1.real = 3749\n"));
+
+        let not_connected = || Err(AocError::http("Not connected"));
+
+        assert_eq!(submit_and_record(&answers_path, 7, Part::One, Some(1), not_connected).unwrap(), Submission::AlreadyRecorded(3749));
+
+        server.finish();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_requests_are_spaced_out() {
+        let server = MockServer::start(vec![("/2024/day/1/input", 200, "1"), ("/2024/day/2/input", 200, "2")], 2);
+        let directory = cache_directory("interval");
+        let mut client = AocClient::new(&server.base_url, "abc123", &directory)
+            .unwrap()
+            .with_request_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.fetch_input(1).unwrap();
+        client.fetch_input(2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        server.finish();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_separate_clients_share_the_interval() {
+        let server = MockServer::start(vec![("/2024/day/1/input", 200, "1"), ("/2024/day/2/input", 200, "2")], 2);
+        let directory = cache_directory("shared-interval");
+        let new_client = || {
+            AocClient::new(&server.base_url, "abc123", &directory)
+                .unwrap()
+                .with_request_interval(Duration::from_millis(200))
+        };
+
+        let start = Instant::now();
+        new_client().fetch_input(1).unwrap();
+        new_client().fetch_input(2).unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!(directory.join(LAST_REQUEST_FILE_NAME).exists());
+
+        server.finish();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test_case("<article><p>That's the right answer!  You are one gold star closer.</p></article>", SubmissionOutcome::Correct)]
+    #[test_case(
+        "<article><p>That's not the right answer; your answer is too high.  Please wait 5 minutes before trying again.</p></article>",
+        SubmissionOutcome::Incorrect { hint: Some(AnswerHint::TooHigh), wait: Some(Duration::from_secs(300)) }
+    )]
+    #[test_case(
+        "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>",
+        SubmissionOutcome::Incorrect { hint: None, wait: None }
+    )]
+    #[test_case(
+        "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>",
+        SubmissionOutcome::RateLimited { wait: Some(Duration::from_secs(65)) }
+    )]
+    #[test_case(
+        "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        SubmissionOutcome::WrongLevel
+    )]
+    #[test_case("<article><p>Something <em>else</em></p></article>", SubmissionOutcome::Unrecognised("Something else".to_string()))]
+    fn test_parse_submission_response(html: &str, expected_outcome: SubmissionOutcome) {
+        assert_eq!(parse_submission_response(html), expected_outcome);
+    }
+
+    #[test]
+    fn test_parse_chunked_response() {
+        let response = parse_http_response("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1 2 \r\n3\r\n3 4\r\n0\r\n\r\n").unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1 2 3 4");
+    }
+
+    #[test_case("http://localhost:8080/aoc/", "http://localhost:8080/aoc/2024/day/1/input")]
+    #[test_case("https://adventofcode.com", "https://adventofcode.com:443/2024/day/1/input")]
+    fn test_base_url(url: &str, expected_url: &str) {
+        assert_eq!(BaseUrl::parse(url).unwrap().url("/2024/day/1/input"), expected_url);
+    }
+
+    #[test_case("ftp://example.com", "abc")]
+    #[test_case("http://:80", "abc")]
+    #[test_case("http://localhost", "abc\r\nX-Injected: 1")]
+    #[test_case("http://localhost", "")]
+    fn test_invalid_client(url: &str, session: &str) {
+        assert!(AocClient::new(url, session, &cache_directory("invalid")).is_err());
+    }
+}
@@ -2,30 +2,50 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
+use crate::error::{parse_lines, AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
 use crate::util::*;
 
-pub fn run_first(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 1, None);
-    let (mut first_array, mut second_array) = get_input_arrays(lines);
+pub struct Dec01;
 
-    first_array.sort();
-    second_array.sort();
+impl Solution for Dec01 {
+    type Input = (Vec<i32>, Vec<i32>);
 
-    let distance = get_distance(first_array, second_array);
+    const DAY: u32 = 1;
 
-    distance
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let pairs = parse_lines(text, parse_location_id_pair)?;
+
+        Ok(pairs.into_iter().unzip())
+    }
+
+    fn part_one(&self, input: &Self::Input) -> AocResult<Answer> {
+        let (mut first_array, mut second_array) = input.clone();
+
+        first_array.sort();
+        second_array.sort();
+
+        Ok(get_distance(first_array, second_array) as Answer)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> AocResult<Answer> {
+        let (first_array, second_array) = input.clone();
+
+        let lookup_table = create_lookup_table(second_array);
+        
+        Ok(first_array
+            .iter()
+            .map(|x| calculate_similarity_score(*x, &lookup_table))
+            .sum::<i32>() as Answer)
+    }
 }
 
-pub fn run_second(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 1, None);
-    let (first_array, second_array) = get_input_arrays(lines);
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec01.run(Part::One, is_real)
+}
 
-    let lookup_table = create_lookup_table(second_array);
-    
-    first_array
-        .iter()
-        .map(|x| calculate_similarity_score(*x, &lookup_table))
-        .sum()
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec01.run(Part::Two, is_real)
 }
 
 fn calculate_similarity_score(value: i32, lookup_table: &HashMap<i32, i32>) -> i32 {
@@ -43,12 +63,11 @@ fn create_lookup_table(second_array: Vec<i32>) -> HashMap<i32, i32> {
         .collect()
 }
 
-fn get_input_arrays(lines: Vec<String>) -> (Vec<i32>, Vec<i32>) {
-    lines
-        .iter()
-        .map(|l| get_integers_in_string(l))
-        .map(|x| (x[0], x[1]))
-        .unzip()
+fn parse_location_id_pair(line: &str) -> AocResult<(i32, i32)> {
+    match get_integers_in_string(line)?[..] {
+        [first, second] => Ok((first, second)),
+        _ => Err(AocError::parse("Expected two location IDs")),
+    }
 }
 
 fn get_distance(first_array: Vec<i32>, second_array: Vec<i32>) -> i32 {
@@ -62,15 +81,16 @@ fn get_distance(first_array: Vec<i32>, second_array: Vec<i32>) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ParseError;
 
     #[test]
     fn test_run_one() {
-        assert_eq!(run_first(false), 11);
+        assert_eq!(run_first(false).unwrap(), 11);
     }
 
     #[test]
     fn real_run_one() {
-        assert_eq!(run_first(true), 2756096);
+        assert_eq!(run_first(true).unwrap(), 2756096);
     }
 
     #[test]
@@ -98,8 +118,15 @@ mod tests {
         assert_eq!(calculate_similarity_score(4, &lookup_table), 4);
     }
 
+    #[test]
+    fn test_parse_reports_malformed_line() {
+        let result = Dec01.parse("3   4\n4\n2   5");
+
+        assert!(matches!(result, Err(AocError::Parse(ParseError { line_number: Some(2), .. }))));
+    }
+
     #[test]
     fn test_run_two() {
-        assert_eq!(run_second(false), 31);
+        assert_eq!(run_second(false).unwrap(), 31);
     }
 }
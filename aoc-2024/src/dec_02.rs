@@ -1,24 +1,42 @@
+use crate::error::{parse_lines, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
 use crate::util::*;
 
-pub fn run_first(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 2, None);
+pub struct Dec02;
 
-    lines
-        .iter()
-        .filter(|l| is_valid(&get_integers_in_string(l)))
-        .count() as i32
-}
+impl Solution for Dec02 {
+    type Input = Vec<Vec<i32>>;
+
+    const DAY: u32 = 2;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        parse_lines(text, get_integers_in_string)
+    }
 
-pub fn run_second(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 2, None);
+    fn part_one(&self, reports: &Self::Input) -> AocResult<Answer> {
+        Ok(reports
+            .iter()
+            .filter(|r| is_valid(r))
+            .count() as Answer)
+    }
 
-    lines
-        .iter()
-        .filter(|l| remove_one_value_from_array(&get_integers_in_string(l))
+    fn part_two(&self, reports: &Self::Input) -> AocResult<Answer> {
+        Ok(reports
             .iter()
-            .any(|x| is_valid(x))
-        )
-        .count() as i32
+            .filter(|r| remove_one_value_from_array(r)
+                .iter()
+                .any(|x| is_valid(x))
+            )
+            .count() as Answer)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec02.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec02.run(Part::Two, is_real)
 }
 
 fn is_valid(array: &[i32]) -> bool {
@@ -48,19 +66,19 @@ fn remove_one_value_from_array(array: &[i32]) -> Vec<Vec<i32>> {
 
         let mut k = 0;
 
-        for j in 0..array_length {
+        for (j, value) in array.iter().enumerate() {
             if j == i {
                 continue;
             }
 
-            output_array[k] = array[j];
-            k = k + 1;
+            output_array[k] = *value;
+            k += 1;
         }
 
         result_arrays.push(output_array);
     }
 
-    return result_arrays;
+    result_arrays
 }
 
 #[cfg(test)]
@@ -107,16 +125,16 @@ mod tests {
 
     #[test]
     fn test_run_one() {
-        assert_eq!(run_first(false), 2);
+        assert_eq!(run_first(false).unwrap(), 2);
     }
 
     #[test]
     fn real_run_one() {
-        assert_eq!(run_first(true), 332);
+        assert_eq!(run_first(true).unwrap(), 332);
     }
 
     #[test]
     fn test_run_two() {
-        assert_eq!(run_second(false), 4);
+        assert_eq!(run_second(false).unwrap(), 4);
     }
 }
@@ -1,29 +1,43 @@
-use crate::util::*;
-
-pub fn run_first(is_real: bool) -> i32 {
-    let order = match is_real {
-        true => None,
-        false => Some(1),
-    };
-    let all_text = read_single_string_from_file(is_real, 3, order);
-    
-    find_multiplications(&all_text)
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::parsing::LazyRegex;
+
+pub struct Dec03;
+
+impl Solution for Dec03 {
+    type Input = String;
+
+    const DAY: u32 = 3;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        Ok(text.to_string())
+    }
+
+    fn part_one(&self, all_text: &Self::Input) -> AocResult<Answer> {
+        Ok(find_multiplications(all_text) as Answer)
+    }
+
+    fn part_two(&self, all_text: &Self::Input) -> AocResult<Answer> {
+        Ok(find_multiplication_strings(all_text) as Answer)
+    }
+
+    fn test_input_order(part: Part) -> Option<u32> {
+        Some(part.number())
+    }
 }
 
-pub fn run_second(is_real: bool) -> i32 {
-    let order = match is_real {
-        true => None,
-        false => Some(2),
-    };
-    let all_text = read_single_string_from_file(is_real, 3, order);
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec03.run(Part::One, is_real)
+}
 
-    find_multiplication_strings(&all_text)
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec03.run(Part::Two, is_real)
 }
 
 fn find_multiplications(input_str: &str) -> i32 {
-    let rgx = regex::Regex::new(r"mul\((\d+),(\d+)\)").unwrap();
+    static MULTIPLICATION: LazyRegex = LazyRegex::new(r"mul\((\d+),(\d+)\)");
 
-    rgx
+    MULTIPLICATION
         .captures_iter(input_str)
         .filter_map(|caps| {
             let (_, [multiplicant_1, multiplicant_2]) = caps.extract();
@@ -36,22 +50,22 @@ fn find_multiplications(input_str: &str) -> i32 {
 }
 
 fn parse_single_multiplication_string(mul_str: &str) -> Option<(i32, i32)> {
-    let rgx = regex::Regex::new(r"\d+").unwrap();
-    let mut match_iterator = rgx.find_iter(mul_str);
+    static NUMBER: LazyRegex = LazyRegex::new(r"\d+");
+    let mut match_iterator = NUMBER.find_iter(mul_str);
 
-    let first_value = match_iterator.next().map(|m| m.as_str().parse::<i32>().unwrap())?;
-    let second_value = match_iterator.next().map(|m| m.as_str().parse::<i32>().unwrap())?;
+    let first_value = match_iterator.next().and_then(|m| m.as_str().parse::<i32>().ok())?;
+    let second_value = match_iterator.next().and_then(|m| m.as_str().parse::<i32>().ok())?;
 
     Some((first_value, second_value))
 }
 
 fn find_multiplication_strings(input_str: &str) -> i32 {
-    let rgx = regex::Regex::new(r"do\(\)|don't\(\)|mul\((\d+),(\d+)\)").unwrap();
+    static INSTRUCTION: LazyRegex = LazyRegex::new(r"do\(\)|don't\(\)|mul\((\d+),(\d+)\)");
 
-    rgx
+    INSTRUCTION
         .find_iter(input_str)
         .map(|m| m.as_str())
-        .map(|s| try_parse_match_str(s).unwrap())
+        .filter_map(try_parse_match_str)
         .fold(ProgramState::new(), |state, v| {
             match v {
                 MultiplicationMatch::Activate => state.activate(),
@@ -115,15 +129,15 @@ fn try_parse_match_str(match_str: &str) -> Option<MultiplicationMatch> { // todo
 }
 
 fn parse_activate_str(match_str: &str) -> Option<MultiplicationMatch> {
-    let rgx = regex::Regex::new(r"do\(\)").unwrap();
+    static ACTIVATE: LazyRegex = LazyRegex::new(r"do\(\)");
     
-    rgx.find(match_str).map(|_x| MultiplicationMatch::Activate)
+    ACTIVATE.find(match_str).map(|_x| MultiplicationMatch::Activate)
 }
 
 fn parse_deactivate_str(match_str: &str) -> Option<MultiplicationMatch> {
-    let rgx = regex::Regex::new(r"don't\(\)").unwrap();
+    static DEACTIVATE: LazyRegex = LazyRegex::new(r"don't\(\)");
     
-    rgx.find(match_str).map(|_x| MultiplicationMatch::Deactivate)
+    DEACTIVATE.find(match_str).map(|_x| MultiplicationMatch::Deactivate)
 }
 
 fn parse_multiply_str(match_str: &str) -> Option<MultiplicationMatch> {
@@ -137,12 +151,12 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 161);
+        assert_eq!(run_first(false).unwrap(), 161);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 191183308);
+        assert_eq!(run_first(true).unwrap(), 191183308);
     }
 
     #[test_case("mul(2,4)", (2, 4))]
@@ -157,7 +171,7 @@ mod tests {
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 48);
+        assert_eq!(run_second(false).unwrap(), 48);
     }
 
     #[test_case("do()", Some(MultiplicationMatch::Activate))]
@@ -1,25 +1,34 @@
-use std::collections::HashMap;
-
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::transform::{GridWindow, Transformable, Transformation};
 use crate::util::*;
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 4, None);
+pub struct Dec04;
 
-    let letter_map = LetterMap::new(lines);
+impl Solution for Dec04 {
+    type Input = LetterMap;
 
-    letter_map.count_all_words_in_all_direction()
-}
+    const DAY: u32 = 4;
 
-pub fn run_second(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 4, None);
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        LetterMap::new(split_lines(text))
+    }
 
-    let letter_map = LetterMap::new(lines);
+    fn part_one(&self, letter_map: &Self::Input) -> AocResult<Answer> {
+        Ok(letter_map.count_all_words_in_all_direction() as Answer)
+    }
 
-    letter_map
-        .letter_map
-        .keys()
-        .filter(|x| letter_map.check_cross_mas_pattern(*x))
-        .count()
+    fn part_two(&self, letter_map: &Self::Input) -> AocResult<Answer> {
+        Ok(letter_map.count_cross_mas_patterns() as Answer)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec04.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec04.run(Part::Two, is_real)
 }
 
 const FIRST_LETTER: char = 'X';
@@ -27,32 +36,27 @@ const SECOND_LETTER: char = 'M';
 const THIRD_LETTER: char = 'A';
 const FOURTH_LETTER: char = 'S';
 
-// todo: use LocationMap from util.rs
-struct LetterMap {
-    letter_map: HashMap<Position, char>,
-    width: usize,
-    height: usize,
+// Any letter may appear in the cells marked with a dot
+const CROSS_MAS_PATTERN: &str = "M.S\n.A.\nM.S";
+const WILDCARD: char = '.';
+
+pub struct LetterMap {
+    letter_map: Grid<char>,
 }
 
 impl LetterMap {
-    fn new(lines: Vec<String>) -> Self {
-        let width = lines[0].len();
-        let height = lines.len();
-
-        let letter_map = position_map_from_text_lines(
-            &lines, 
-            |c| c,
-        );
-        
-        LetterMap { letter_map, width, height, }
-    }
+    fn new(lines: Vec<String>) -> AocResult<Self> {
+        if lines.is_empty() {
+            return Err(AocError::parse("The word search is empty"));
+        }
 
-    fn get_char(&self, position: &Position) -> Option<char> {
-        self.letter_map.get(position).map(|x| *x)
+        let letter_map = Grid::from_text_lines(&lines, Some)?;
+        
+        Ok(LetterMap { letter_map })
     }
 
     fn count_all_words_in_all_direction(&self) -> usize {
-        self.letter_map.keys().into_iter().map(|position| self.count_words_in_all_directions(position)).sum()
+        self.letter_map.positions().map(|position| self.count_words_in_all_directions(&position)).sum()
     }
 
     fn count_words_in_all_directions(&self, first_letter_index: &Position) -> usize {
@@ -84,52 +88,48 @@ impl LetterMap {
         false
     }
 
-    fn check_cross_mas_pattern(&self, position: &Position) -> bool {
-        let Some(c_letter) = self.get_char(position) else { return false };
-        
-        if c_letter != 'A' {
-            return false;
-        }
-        
-        // todo: this is too complicated
-        let nw_letter = position.new_position(&Direction::NW.get_movement()).map(|x| self.get_char(&x)).flatten();
-        let ne_letter = position.new_position(&Direction::NE.get_movement()).map(|x| self.get_char(&x)).flatten();
-        let se_letter = position.new_position(&Direction::SE.get_movement()).map(|x| self.get_char(&x)).flatten();
-        let sw_letter = position.new_position(&Direction::SW.get_movement()).map(|x| self.get_char(&x)).flatten();
-
-        let Some(nw_letter) = nw_letter else { return false };
-        let Some(ne_letter) = ne_letter else { return false };
-        let Some(se_letter) = se_letter else { return false };
-        let Some(sw_letter) = sw_letter else { return false };
-
-        let nw_to_se_correct = nw_letter == 'M' && se_letter == 'S' || nw_letter == 'S' && se_letter == 'M';
-        let sw_to_ne_correct = sw_letter == 'M' && ne_letter == 'S' || sw_letter == 'S' && ne_letter == 'M';
-
-        nw_to_se_correct && sw_to_ne_correct
+    fn count_cross_mas_patterns(&self) -> usize {
+        let pattern = Grid::from_text_lines(&split_lines(CROSS_MAS_PATTERN), Some).unwrap();
+        let oriented_patterns = Transformation::rotations()
+            .iter()
+            .map(|t| pattern.transformed(*t))
+            .collect::<Vec<Grid<char>>>();
+
+        self.letter_map
+            .window_views(pattern.width(), pattern.height())
+            .filter(|window| oriented_patterns.iter().any(|p| matches_pattern(window, p)))
+            .count()
     }
 }
 
+fn matches_pattern(window: &GridWindow<char>, pattern: &Grid<char>) -> bool {
+    window
+        .iter()
+        .zip(pattern.iter())
+        .all(|((_, letter), (_, pattern_letter))| *pattern_letter == WILDCARD || letter == pattern_letter)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_run_one() {
-        assert_eq!(run_first(false), 18);
+        assert_eq!(run_first(false).unwrap(), 18);
     }
 
     #[test]
     fn real_run_one() {
-        assert_eq!(run_first(true), 2507);
+        assert_eq!(run_first(true).unwrap(), 2507);
     }
 
     #[test]
     fn test_run_two() {
-        assert_eq!(run_second(false), 9);
+        assert_eq!(run_second(false).unwrap(), 9);
     }
 
     #[test]
     fn real_run_two() {
-        assert_eq!(run_second(true), 1969);
+        assert_eq!(run_second(true).unwrap(), 1969);
     }
 }
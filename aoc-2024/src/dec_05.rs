@@ -1,73 +1,78 @@
-use crate::util::*;
-
-pub fn run_first(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 5, None);
-
-    let order_rules = lines
-        .iter()
-        .filter_map(|l| parse_order_rule(l))
-        .collect::<Vec<OrderRule>>();
-    
-    lines
-        .iter()
-        .filter_map(|l| parse_printing_update(&l))
-        .filter(|pu| pu.fulfills_rules(&order_rules))
-        .map(|pu| pu.get_middle_number())
-        .sum()
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::parsing::{LinePattern, List};
+use crate::util::sections::split_fixed_sections;
+
+pub struct Dec05;
+
+impl Solution for Dec05 {
+    type Input = (Vec<OrderRule>, Vec<PrintingUpdate>);
+
+    const DAY: u32 = 5;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let [order_rule_section, printing_update_section] = split_fixed_sections(text)?;
+
+        let order_rules = order_rule_section.parse_lines(parse_order_rule)?;
+        let printing_updates = printing_update_section.parse_lines(parse_printing_update)?;
+
+        Ok((order_rules, printing_updates))
+    }
+
+    fn part_one(&self, (order_rules, printing_updates): &Self::Input) -> AocResult<Answer> {
+        Ok(printing_updates
+            .iter()
+            .filter(|pu| pu.fulfills_rules(order_rules))
+            .map(|pu| pu.get_middle_number())
+            .sum::<i32>() as Answer)
+    }
+
+    fn part_two(&self, (order_rules, printing_updates): &Self::Input) -> AocResult<Answer> {
+        let mut printing_updates = printing_updates
+            .iter()
+            .filter(|pu| !pu.fulfills_rules(order_rules))
+            .cloned()
+            .collect::<Vec<PrintingUpdate>>();
+
+        printing_updates
+            .iter_mut()
+            .for_each(|pu| pu.order(order_rules));
+
+        Ok(printing_updates
+            .iter()
+            .map(|pu| pu.get_middle_number())
+            .sum::<i32>() as Answer)
+    }
 }
 
-pub fn run_second(is_real: bool) -> i32 {
-    let lines = read_from_file(is_real, 5, None);
-
-    let order_rules = lines
-        .iter()
-        .filter_map(|l| parse_order_rule(l))
-        .collect::<Vec<OrderRule>>();
-    let mut printing_updates = lines
-        .iter()
-        .filter_map(|l| parse_printing_update(&l))
-        .filter(|pu| !pu.fulfills_rules(&order_rules))
-        .collect::<Vec<PrintingUpdate>>();
-
-    printing_updates
-        .iter_mut()
-        .for_each(|pu| pu.order(&order_rules));
-
-    printing_updates
-        .iter()
-        .map(|pu| pu.get_middle_number())
-        .sum()
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec05.run(Part::One, is_real)
 }
 
-fn parse_order_rule(rule_str: &str) -> Option<OrderRule> {
-    let rgx = regex::Regex::new(r"(\d+)\|(\d+)").ok()?;
-    let _m = rgx.find(rule_str)?;
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec05.run(Part::Two, is_real)
+}
 
-    let rgx = regex::Regex::new(r"(\d+)").ok()?;
-    let mut matches = rgx.find_iter(rule_str);
+static ORDER_RULE: LinePattern = LinePattern::described_as("{}|{}", "an order rule like 47|53");
+static PRINTING_UPDATE: LinePattern = LinePattern::described_as("{},{}", "a printing update like 75,47,61");
 
-    let before = matches.next()?.as_str().parse::<i32>().ok()?;
-    let after = matches.next()?.as_str().parse::<i32>().ok()?;
-    
-    Some(OrderRule { before, after })
-}
+fn parse_order_rule(rule_str: &str) -> AocResult<OrderRule> {
+    let (before, after) = ORDER_RULE.parse::<(i32, i32)>(rule_str)?;
 
-fn parse_printing_update(rule_str: &str) -> Option<PrintingUpdate> {
-    let rgx = regex::Regex::new(r"(\d+,+\d+)").ok()?;
-    let _m = rgx.find(rule_str)?;
+    Ok(OrderRule { before, after })
+}
 
-    let rgx = regex::Regex::new(r"\d+").ok()?;
+// An update has at least two pages
+fn parse_printing_update(rule_str: &str) -> AocResult<PrintingUpdate> {
+    let (first_page, List(other_pages)) = PRINTING_UPDATE.parse::<(i32, List<i32>)>(rule_str)?;
 
-    let pages = rgx
-        .find_iter(rule_str)
-        .filter_map(|m| m.as_str().parse::<i32>().ok())
-        .collect::<Vec<i32>>();
+    let pages = std::iter::once(first_page).chain(other_pages).collect::<Vec<i32>>();
 
-    Some(PrintingUpdate { pages })
+    Ok(PrintingUpdate { pages })
 }
 
 #[derive(Debug, PartialEq)]
-struct OrderRule {
+pub struct OrderRule {
     before: i32,
     after: i32,
 }
@@ -83,8 +88,8 @@ impl OrderRule {
     }
 }
 
-#[derive(Debug, PartialEq)]
-struct PrintingUpdate {
+#[derive(Debug, PartialEq, Clone)]
+pub struct PrintingUpdate {
     pages: Vec<i32>,
 }
 
@@ -119,11 +124,12 @@ impl PrintingUpdate {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::{AocError, ParseError};
     use test_case::test_case;
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 143);
+        assert_eq!(run_first(false).unwrap(), 143);
     }
 
     #[test_case("47|53", Some( OrderRule { before: 47, after: 53 } ))]
@@ -131,7 +137,7 @@ mod tests {
     #[test_case("", None)]
     #[test_case("75,47,61,53,29", None)]
     fn test_parse_order_rule(maybe_rule_str: &str, expected_result: Option<OrderRule>) {
-        let result = parse_order_rule(maybe_rule_str);
+        let result = parse_order_rule(maybe_rule_str).ok();
 
         assert_eq!(result, expected_result)
     }
@@ -140,7 +146,7 @@ mod tests {
     #[test_case("", None)]
     #[test_case("34|64", None)]
     fn test_parse_printing_update(maybe_printing_update_str: &str, expected_result: Option<PrintingUpdate>) {
-        let result = parse_printing_update(maybe_printing_update_str);
+        let result = parse_printing_update(maybe_printing_update_str).ok();
 
         assert_eq!(result, expected_result)
     }
@@ -160,14 +166,21 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn test_parse_reports_unknown_line() {
+        let result = Dec05.parse("47|53\n\n75,47,61\n75;47");
+
+        assert!(matches!(result, Err(AocError::Parse(ParseError { line_number: Some(4), section: Some(2), .. }))));
+    }
+
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 4814);
+        assert_eq!(run_first(true).unwrap(), 4814);
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 123);
+        assert_eq!(run_second(false).unwrap(), 123);
     }
 
     #[test_case(OrderRule { before: 47, after: 53 }, &mut [53, 2, 47], &[47, 2, 53])]
@@ -179,7 +192,7 @@ mod tests {
 
     #[test]
     fn test_order_printing_update() {
-        let expected_result = vec![1, 4, 5];
+        let expected_result = [1, 4, 5];
 
         let mut printing_update = PrintingUpdate { pages: vec![4, 5, 1] };
         let order_rules = vec![OrderRule { before: 1, after: 4 }, OrderRule { before: 4, after: 5 }];
@@ -1,69 +1,84 @@
-use std::collections::HashMap;
-
 use itertools::Itertools;
 
-use crate::util::{position_and_object_from_text_lines, position_map_from_text_lines, positions_on_map_with_value, read_from_file, Direction, Position};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::{position_and_object_from_text_lines, split_lines, Direction, Grid, Position};
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 6, None);
+pub struct Dec06;
 
-    let location_map = position_map_from_text_lines(&lines, |c| Location::from_char(c));
-    let (position, direction) = position_and_object_from_text_lines(&lines, |c| Direction::from_char(c)).unwrap();
-    let guard = Guard { position, bearing: direction };
+impl Solution for Dec06 {
+    type Input = (Grid<Location>, Guard);
 
-    count_number_of_unique_positions_on_way_out(
-        &guard, 
-        &location_map
-    ).unwrap()
-}
+    const DAY: u32 = 6;
 
-pub fn run_second(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 6, None);
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let lines = split_lines(text);
 
-    let mut location_map = position_map_from_text_lines(&lines, |c| Location::from_char(c));
-    let (position, direction) = position_and_object_from_text_lines(&lines, |c| Direction::from_char(c)).unwrap();
-    let guard = Guard { position, bearing: direction };
+        let location_map = Grid::from_text_lines(&lines, Location::from_char)?;
+        let (position, direction) = position_and_object_from_text_lines(&lines, Direction::from_char)
+            .ok_or(AocError::parse("No guard found on the map"))?;
+        let guard = Guard { position, bearing: direction };
 
-    let mut obstruction_loops = 0;
+        Ok((location_map, guard))
+    }
 
-    let free_positions = positions_on_map_with_value(
-        &location_map, 
-        Location::Free
-    );
+    fn part_one(&self, (location_map, guard): &Self::Input) -> AocResult<Answer> {
+        count_number_of_unique_positions_on_way_out(
+            guard, 
+            location_map
+        )
+        .map(|c| c as Answer)
+        .ok_or(AocError::unsolvable("The guard never leaves the map"))
+    }
 
-    for free_position in free_positions {
-        // We can't place an obstruction in the guard's initial position
-        if free_position == guard.position {
-            continue;
-        }
+    fn part_two(&self, (location_map, guard): &Self::Input) -> AocResult<Answer> {
+        let mut location_map = location_map.clone();
+        let mut obstruction_loops = 0;
 
-        location_map.insert(free_position, Location::Obstruction).unwrap();
+        let free_positions = location_map.positions_of(&Location::Free);
 
-        if let None = count_number_of_unique_positions_on_way_out(&guard, &location_map) {
-            obstruction_loops = obstruction_loops + 1;
-        };
+        for free_position in free_positions {
+            // We can't place an obstruction in the guard's initial position
+            if free_position == guard.position {
+                continue;
+            }
 
-        location_map.insert(free_position, Location::Free).unwrap();
+            location_map.set(&free_position, Location::Obstruction);
+
+            if count_number_of_unique_positions_on_way_out(guard, &location_map).is_none() {
+                obstruction_loops += 1;
+            };
+
+            location_map.set(&free_position, Location::Free);
+        }
+
+        Ok(obstruction_loops)
     }
+}
 
-    obstruction_loops
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec06.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec06.run(Part::Two, is_real)
 }
 
 fn count_number_of_unique_positions_on_way_out(
     guard: &Guard,
-    location_map: &HashMap<Position, Location>,
+    location_map: &Grid<Location>,
 ) -> Option<usize> {
-    let mut guard = guard.clone();
+    let mut guard = *guard;
     let mut guards = vec![guard];
     let mut guard_counter = 0;
 
-    while let Some(guard_on_map) = guard.get_next(&location_map) {
+    while let Some(guard_on_map) = guard.get_next(location_map) {
         if guard_counter % 2000 == 0 && guards.contains(&guard_on_map) {
             return None;
         }
 
         guards.push(guard_on_map);
-        guard_counter = guard_counter + 1;
+        guard_counter += 1;
         guard = guard_on_map;
     }
 
@@ -74,24 +89,24 @@ fn count_number_of_unique_positions_on_way_out(
         .count())
 }
 
-#[derive(PartialEq)]
-enum Location {
+#[derive(PartialEq, Clone)]
+pub enum Location {
     Obstruction,
     Free,
 }
 
 impl Location {
-    fn from_char(c: char) -> Self {
+    fn from_char(c: char) -> Option<Self> {
         match c {
-            '#' => Location::Obstruction,
-            '.'|'>'|'<'|'^'|'v' => Location::Free,
-            _ => panic!("Invalid char: {}", c),
+            '#' => Some(Location::Obstruction),
+            '.'|'>'|'<'|'^'|'v' => Some(Location::Free),
+            _ => None,
         }
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
-struct Guard {
+pub struct Guard {
     position: Position,
     bearing: Direction,
 }
@@ -99,7 +114,7 @@ struct Guard {
 impl Guard {
     fn get_next(
         &self, 
-        location_map: &HashMap<Position, Location>,
+        location_map: &Grid<Location>,
     ) -> Option<Guard> {
         let next_square = self.position.new_position(&self.bearing.get_movement())?;
 
@@ -114,19 +129,35 @@ impl Guard {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ParseError;
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 41);
+        assert_eq!(run_first(false).unwrap(), 41);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 5453)
+        assert_eq!(run_first(true).unwrap(), 5453)
+    }
+
+    #[test]
+    fn test_parse_reports_unexpected_character() {
+        let result = Dec06.parse("..#.\n.^..\n..x.");
+
+        assert!(matches!(
+            result, 
+            Err(AocError::Parse(ParseError { line_number: Some(3), column: Some(3), .. }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_without_guard() {
+        assert!(Dec06.parse("..#.\n....").is_err());
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 6);
+        assert_eq!(run_second(false).unwrap(), 6);
     }
 }
@@ -1,24 +1,45 @@
-use crate::util::read_from_file;
+use crate::error::{parse_non_empty_lines, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::parsing::{LinePattern, List};
 
-pub fn run_first(is_real: bool) -> usize {
-    solve(is_real, false)
+pub struct Dec07;
+
+impl Solution for Dec07 {
+    type Input = Vec<CalibrationLine>;
+
+    const DAY: u32 = 7;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        parse_non_empty_lines(text, parse_calibration_result_line)
+    }
+
+    fn part_one(&self, calibration_lines: &Self::Input) -> AocResult<Answer> {
+        Ok(solve(calibration_lines, false) as Answer)
+    }
+
+    fn part_two(&self, calibration_lines: &Self::Input) -> AocResult<Answer> {
+        Ok(solve(calibration_lines, true) as Answer)
+    }
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    solve(is_real, true)
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec07.run(Part::One, is_real)
 }
 
-fn solve(is_real: bool, allow_concatenation: bool) -> usize {
-    read_from_file(is_real, 7, None)
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec07.run(Part::Two, is_real)
+}
+
+fn solve(calibration_lines: &[CalibrationLine], allow_concatenation: bool) -> usize {
+    calibration_lines
         .iter()
-        .filter_map(|x| parse_calibration_result_line(x))
         .filter(|cl| cl.result_can_be_calculated_from_inputs(allow_concatenation))
         .map(|cl| cl.result)
         .sum::<usize>()
 }
 
 #[derive(Debug)]
-struct CalibrationLine {
+pub struct CalibrationLine {
     result: usize,
     inputs: Vec<usize>,
 }
@@ -57,7 +78,7 @@ impl ResultNode {
         rest_of_inputs: &[usize],
         allow_concatenation: bool,
     ) -> Self {
-        if rest_of_inputs.len() == 0 {
+        if rest_of_inputs.is_empty() {
             return Self {
                 node_type: ResultNodeType::Result(temporary_result),
             };
@@ -113,11 +134,11 @@ impl ResultNode {
                     None => Vec::new(),
                 };
 
-                return addition_node_results.iter()
+                addition_node_results.iter()
                     .chain(multiplication_node_results.iter())
                     .chain(concatenation_node_results.iter())
-                    .map(|x| *x)
-                    .collect::<Vec<usize>>();
+                    .copied()
+                    .collect::<Vec<usize>>()
             },
         }
     }
@@ -144,19 +165,12 @@ fn concatenate_usize(first: usize, second: usize) -> usize {
     first * first_multiplier + second
 }
 
-fn parse_calibration_result_line(line: &str) -> Option<CalibrationLine> {
-    let rgx = regex::Regex::new(r"(\d+): ((\d+) )+(\d+)").ok()?;
-    let _m = rgx.find(line)?;
-
-    let rgx = regex::Regex::new(r"(\d+)").ok()?;
-    let mut matches = rgx.find_iter(line);
+static CALIBRATION_LINE: LinePattern = LinePattern::described_as("{}: {}", "a calibration line like 190: 10 19");
 
-    let result = matches.next()?.as_str().parse::<usize>().ok()?;
-    let inputs = matches
-        .filter_map(|m| m.as_str().parse::<usize>().ok())
-        .collect();
+fn parse_calibration_result_line(line: &str) -> AocResult<CalibrationLine> {
+    let (result, List(inputs)) = CALIBRATION_LINE.parse::<(usize, List<usize>)>(line)?;
 
-    Some(CalibrationLine { result, inputs, })
+    Ok(CalibrationLine { result, inputs })
 }
 
 #[cfg(test)]
@@ -166,17 +180,17 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 3749);
+        assert_eq!(run_first(false).unwrap(), 3749);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 42283209483350)
+        assert_eq!(run_first(true).unwrap(), 42283209483350)
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 11387);
+        assert_eq!(run_second(false).unwrap(), 11387);
     }
 
     #[test_case(12, 0, 120)]
@@ -1,57 +1,66 @@
 use itertools::Itertools;
 
-use crate::util::{get_position_map_dimensions, position_map_from_text_lines, positions_on_map_with_value, read_from_file, Dimensions, Position};
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::lines::{lattice_points, LineExtent};
+use crate::util::{split_lines, Dimensions, Grid, Position};
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 8, None);
+pub struct Dec08;
 
-    let antenna_map = position_map_from_text_lines(
-        &lines, 
-        |c| AntennaLocation::parse_from_char(c));
+impl Solution for Dec08 {
+    type Input = Grid<AntennaLocation>;
 
-    let antenna_map_dimensions = get_position_map_dimensions(&antenna_map).unwrap();
-    let unique_antenna_frequencies = antenna_map
-        .values()
-        .filter_map(|p| p.frequency())
-        .unique()
-        .collect::<Vec<char>>();
-    
-    unique_antenna_frequencies
-        .iter()
-        .flat_map(|f| 
-            positions_on_map_with_value(&antenna_map, AntennaLocation::Antenna(*f))
-                .iter()
-                .combinations(2)
-                .map(|x| AntennaCouple { first: *x[0], second: *x[1]})
-                .flat_map(|ac| ac.get_antinode_positions_without_regard_to_resonant_harmonics(&antenna_map_dimensions))
-                .collect::<Vec<Position>>()
-        )
-        .unique()
-        .count()
+    const DAY: u32 = 8;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        Grid::from_text_lines(
+            &split_lines(text), 
+            AntennaLocation::parse_from_char)
+    }
+
+    fn part_one(&self, antenna_map: &Self::Input) -> AocResult<Answer> {
+        Ok(count_unique_antinode_positions(
+            antenna_map, 
+            AntennaCouple::get_antinode_positions_without_regard_to_resonant_harmonics,
+        ) as Answer)
+    }
+
+    fn part_two(&self, antenna_map: &Self::Input) -> AocResult<Answer> {
+        Ok(count_unique_antinode_positions(
+            antenna_map, 
+            AntennaCouple::get_antinode_positions_with_regard_to_resonant_harmonics,
+        ) as Answer)
+    }
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 8, None);
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec08.run(Part::One, is_real)
+}
 
-    let antenna_map = position_map_from_text_lines(
-        &lines, 
-        |c| AntennaLocation::parse_from_char(c));
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec08.run(Part::Two, is_real)
+}
 
-    let antenna_map_dimensions = get_position_map_dimensions(&antenna_map).unwrap();
+fn count_unique_antinode_positions(
+    antenna_map: &Grid<AntennaLocation>,
+    get_antinode_positions: fn(&AntennaCouple, &Dimensions) -> Vec<Position>,
+) -> usize {
+    let antenna_map_dimensions = antenna_map.dimensions();
     let unique_antenna_frequencies = antenna_map
-        .values()
-        .filter_map(|p| p.frequency())
+        .iter()
+        .filter_map(|(_, l)| l.frequency())
         .unique()
         .collect::<Vec<char>>();
     
     unique_antenna_frequencies
         .iter()
         .flat_map(|f| 
-            positions_on_map_with_value(&antenna_map, AntennaLocation::Antenna(*f))
+            antenna_map
+                .positions_of(&AntennaLocation::Antenna(*f))
                 .iter()
                 .combinations(2)
                 .map(|x| AntennaCouple { first: *x[0], second: *x[1]})
-                .flat_map(|ac| ac.get_antinode_positions_with_regard_to_resonant_harmonics(&antenna_map_dimensions))
+                .flat_map(|ac| get_antinode_positions(&ac, &antenna_map_dimensions))
                 .collect::<Vec<Position>>()
         )
         .unique()
@@ -59,22 +68,22 @@ pub fn run_second(is_real: bool) -> usize {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
-enum AntennaLocation {
+pub enum AntennaLocation {
     Free,
     Antenna(char),
 }
 
 impl AntennaLocation {
-    fn parse_from_char(c: char) -> Self {
+    fn parse_from_char(c: char) -> Option<Self> {
         if c.is_alphanumeric() {
-            return AntennaLocation::Antenna(c);
+            return Some(AntennaLocation::Antenna(c));
         }
 
         if c == '.' {
-            return AntennaLocation::Free;
+            return Some(AntennaLocation::Free);
         }
 
-        panic!("Unknown character");
+        None
     }
 
     fn frequency(&self) -> Option<char> {
@@ -114,31 +123,12 @@ impl AntennaCouple {
         antinodes
     }
     
+    // Every grid point in line with both antennas, including the ones between them
     fn get_antinode_positions_with_regard_to_resonant_harmonics(
         &self, 
         map_dimensions: &Dimensions,
     ) -> Vec<Position> {
-        let mut antinodes = vec![self.first, self.second];
-
-        let separation = self.first.separated_from_by(&self.second);
-        let mut positive_antinode = self.first;
-        let mut negative_antinode = self.second;
-
-        while let Some(new_positive_antinode) = positive_antinode
-            .new_position(&separation)
-            .filter(|p| map_dimensions.includes(p)) {
-                antinodes.push(new_positive_antinode);
-                positive_antinode = new_positive_antinode;
-            }
-
-        while let Some(new_negative_antinode) = negative_antinode
-            .new_position(&separation.negative())
-            .filter(|p| map_dimensions.includes(p)) {
-                antinodes.push(new_negative_antinode);
-                negative_antinode = new_negative_antinode;
-            }
-
-        antinodes
+        lattice_points(&self.first, &self.second, LineExtent::Line, map_dimensions)
     }
 }
 
@@ -148,7 +138,7 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 14);
+        assert_eq!(run_first(false).unwrap(), 14);
     }
 
     #[test]
@@ -158,10 +148,8 @@ mod tests {
             second: Position { x: 5, y: 5 }
         };
 
-        let expected_result = vec![
-            Position { x: 3, y: 1 },
-            Position { x: 6, y: 7 }
-        ];
+        let expected_result = [Position { x: 3, y: 1 },
+            Position { x: 6, y: 7 }];
         let result = antenna_couple.get_antinode_positions_without_regard_to_resonant_harmonics(
             &Dimensions { width: 12, height: 12 },
         );
@@ -177,14 +165,12 @@ mod tests {
             second: Position { x: 5, y: 5 }
         };
 
-        let expected_result = vec![
-            Position { x: 3, y: 1 },
+        let expected_result = [Position { x: 3, y: 1 },
             Position { x: 4, y: 3 },
             Position { x: 5, y: 5 },
             Position { x: 6, y: 7 },
             Position { x: 7, y: 9 },
-            Position { x: 8, y: 11 },
-        ];
+            Position { x: 8, y: 11 }];
         let result = antenna_couple.get_antinode_positions_with_regard_to_resonant_harmonics(
             &Dimensions { width: 12, height: 12 },
         );
@@ -197,13 +183,28 @@ mod tests {
         assert!(result.contains(&expected_result[5]));
     }
 
+    #[test]
+    fn test_resonant_harmonics_include_points_between_antennas() {
+        let antenna_couple = AntennaCouple {
+            first: Position { x: 1, y: 1 },
+            second: Position { x: 3, y: 5 }
+        };
+
+        let result = antenna_couple.get_antinode_positions_with_regard_to_resonant_harmonics(
+            &Dimensions { width: 12, height: 12 },
+        );
+
+        assert!(result.contains(&Position { x: 2, y: 3 }));
+        assert_eq!(result.len(), 6);
+    }
+
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 409)
+        assert_eq!(run_first(true).unwrap(), 409)
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 34);
+        assert_eq!(run_second(false).unwrap(), 34);
     }
 }
@@ -1,16 +1,43 @@
 use itertools::Itertools;
 
-use crate::util::read_single_string_from_file;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
 
-pub fn run_first(is_real: bool) -> usize {
-    let disk_map = read_single_string_from_file(is_real, 9, None);
+pub struct Dec09;
+
+impl Solution for Dec09 {
+    type Input = String;
+
+    const DAY: u32 = 9;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        Ok(text.to_string())
+    }
+
+    fn part_one(&self, disk_map: &Self::Input) -> AocResult<Answer> {
+        Ok(compact_memory_spaces(disk_map) as Answer)
+    }
+
+    fn part_two(&self, disk_map: &Self::Input) -> AocResult<Answer> {
+        Ok(compact_memory_blocks(disk_map)? as Answer)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec09.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec09.run(Part::Two, is_real)
+}
+
+fn compact_memory_spaces(disk_map: &str) -> usize {
     let mut memory_space_content_vector = parse_disk_map_to_memory_space_content_vector(disk_map);
 
     let (
         indexed_free_memory_spaces, 
-        indexed_file_memory_spaces): 
-        (Vec<(usize, &MemorySpaceContent)>, Vec<(usize, &MemorySpaceContent)>
-    ) = memory_space_content_vector
+        indexed_file_memory_spaces
+    ): (IndexedMemorySpaceContents, IndexedMemorySpaceContents) = memory_space_content_vector
         .iter()
         .enumerate()
         .partition(|(_, msc)| msc.is_free());
@@ -41,8 +68,7 @@ pub fn run_first(is_real: bool) -> usize {
         .sum()
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    let disk_map = read_single_string_from_file(is_real, 9, None);
+fn compact_memory_blocks(disk_map: &str) -> AocResult<usize> {
     let mut memory_block_vector = parse_disk_map_to_memory_block_vector(disk_map);
 
     let before_total_memory_length = memory_block_vector.iter().map(|x| x.block_size).sum::<usize>();
@@ -72,19 +98,19 @@ pub fn run_second(is_real: bool) -> usize {
         };
         
         memory_block_vector.retain(|mb| mb.start_index != file_memory_block.start_index && mb.start_index != free_memory_block.start_index);
-        memory_block_vector.append(&mut swap_memory_blocks(&free_memory_block, &file_memory_block));
+        memory_block_vector.append(&mut swap_memory_blocks(&free_memory_block, &file_memory_block)?);
     }
 
     let after_total_memory_length = memory_block_vector.iter().map(|x| x.block_size).sum::<usize>();
     assert_eq!(before_total_memory_length, after_total_memory_length);
 
-    memory_block_vector
+    Ok(memory_block_vector
         .iter()
         .map(|x| x.calculate_checksum_value())
-        .sum::<usize>()
+        .sum::<usize>())
 }
 
-fn parse_disk_map_to_memory_block_vector(disk_map: String) -> Vec<MemoryBlock> {
+fn parse_disk_map_to_memory_block_vector(disk_map: &str) -> Vec<MemoryBlock> {
     let mut current_memory_index = 0;
     
     disk_map
@@ -93,10 +119,10 @@ fn parse_disk_map_to_memory_block_vector(disk_map: String) -> Vec<MemoryBlock> {
         .filter_map(|(disk_map_index, block_size_char)| {
             let block_size = block_size_char.to_digit(10)?;
             let previous_memory_location = current_memory_index;
-            current_memory_index = current_memory_index + block_size;
+            current_memory_index += block_size;
 
             if block_size == 0 {
-                return None;
+                None
             } else {
                 Some((disk_map_index, previous_memory_location, block_size))
             }
@@ -105,7 +131,7 @@ fn parse_disk_map_to_memory_block_vector(disk_map: String) -> Vec<MemoryBlock> {
         .collect::<Vec<MemoryBlock>>()
 }
 
-fn parse_disk_map_to_memory_space_content_vector(disk_map: String) -> Vec<MemorySpaceContent> {
+fn parse_disk_map_to_memory_space_content_vector(disk_map: &str) -> Vec<MemorySpaceContent> {
     disk_map
         .chars()
         .enumerate()
@@ -129,11 +155,12 @@ fn disk_map_location_to_repeated_memory_space_contents(disk_map_location: usize,
         },
     };
 
-    std::iter::repeat(repeated_value)
-        .take(cardinality as usize)
+    std::iter::repeat_n(repeated_value, cardinality as usize)
         .collect()
 }
 
+type IndexedMemorySpaceContents<'a> = Vec<(usize, &'a MemorySpaceContent)>;
+
 #[derive(Clone, Copy, Debug)]
 enum MemorySpaceContent {
     File(usize), // ID
@@ -189,7 +216,7 @@ impl MemoryBlock {
 
 impl PartialOrd for MemoryBlock {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.start_index.partial_cmp(&other.start_index)
+        Some(self.cmp(other))
     }
 }
 
@@ -217,7 +244,7 @@ impl MemoryBlockType {
 fn swap_memory_blocks(
     free_memory_block: &MemoryBlock,
     file_memory_block: &MemoryBlock,
-) -> Vec<MemoryBlock> {
+) -> AocResult<Vec<MemoryBlock>> {
     assert!(free_memory_block.block_type.is_free());
     assert!(free_memory_block.block_size >= file_memory_block.block_size);
 
@@ -229,7 +256,10 @@ fn swap_memory_blocks(
 
     let file_id = match file_memory_block.block_type {
         MemoryBlockType::File(id) => id,
-        MemoryBlockType::Free => panic!("Could not retrieve file ID"),
+        MemoryBlockType::Free => return Err(AocError::unsolvable(format!(
+            "Could not retrieve file ID of the block at {}",
+            file_memory_block.start_index,
+        ))),
     };
 
     let swapped_file_memory_block = MemoryBlock {
@@ -241,7 +271,7 @@ fn swap_memory_blocks(
     let block_size_difference = free_memory_block.block_size - file_memory_block.block_size;
 
     if block_size_difference == 0 {
-        return vec![swapped_file_memory_block, swapped_free_memory_block]
+        return Ok(vec![swapped_file_memory_block, swapped_free_memory_block])
     }
 
     let additional_free_memory_block = MemoryBlock {
@@ -250,7 +280,7 @@ fn swap_memory_blocks(
         block_size: block_size_difference,
     };
 
-    vec![swapped_file_memory_block, additional_free_memory_block, swapped_free_memory_block]
+    Ok(vec![swapped_file_memory_block, additional_free_memory_block, swapped_free_memory_block])
 }
 
 #[cfg(test)]
@@ -259,17 +289,17 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 1928);
+        assert_eq!(run_first(false).unwrap(), 1928);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 6301895872542);
+        assert_eq!(run_first(true).unwrap(), 6301895872542);
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 2858);
+        assert_eq!(run_second(false).unwrap(), 2858);
     }
 
     #[test]
@@ -286,8 +316,16 @@ mod tests {
         assert_eq!(result, expected_result);
     }
 
+    #[test]
+    fn test_swapping_a_free_block_is_an_error() {
+        let free_memory_block = MemoryBlock { block_type: MemoryBlockType::Free, start_index: 2, block_size: 3 };
+        let other_free_memory_block = MemoryBlock { block_type: MemoryBlockType::Free, start_index: 7, block_size: 1 };
+
+        assert!(matches!(swap_memory_blocks(&free_memory_block, &other_free_memory_block), Err(AocError::Unsolvable(_))));
+    }
+
     #[test]
     fn real_run_second() {
-        assert_eq!(run_second(true), 6323761685944);
+        assert_eq!(run_second(true).unwrap(), 6323761685944);
     }
 }
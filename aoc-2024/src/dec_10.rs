@@ -1,126 +1,90 @@
-use std::collections::HashMap;
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::search::{self, SearchResult};
+use crate::util::{cardinal_directions, split_lines, Grid, Position};
 
-use itertools::Itertools;
+pub struct Dec10;
 
-use crate::util::{cardinal_directions, position_map_from_text_lines, read_from_file, Direction, Position};
+impl Solution for Dec10 {
+    type Input = Grid<usize>;
 
-pub fn run_first(is_real: bool) -> usize {
-    let trail_map = get_trail_map(is_real);
+    const DAY: u32 = 10;
 
-    calculate_from_trail_map(&trail_map, |trailhead| trailhead.calculate_score())
-}
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let parse_from_char = |c: char| c.to_digit(10).map(|h| h as usize);
 
-pub fn run_second(is_real: bool) -> usize {
-    let trail_map = get_trail_map(is_real);
+        Grid::from_text_lines(&split_lines(text), parse_from_char)
+    }
 
-    calculate_from_trail_map(&trail_map, |trailhead| trailhead.calculate_rating())
+    fn part_one(&self, trail_map: &Self::Input) -> AocResult<Answer> {
+        Ok(calculate_from_trail_map(trail_map, |trailhead| trailhead.calculate_score()) as Answer)
+    }
+
+    fn part_two(&self, trail_map: &Self::Input) -> AocResult<Answer> {
+        Ok(calculate_from_trail_map(trail_map, |trailhead| trailhead.calculate_rating()) as Answer)
+    }
 }
 
-fn get_trail_map(is_real: bool) -> HashMap<Position, usize> {
-    let lines = read_from_file(is_real, 10, None);
-    let parse_from_char= |c: char| c.to_digit(10).unwrap() as usize;
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec10.run(Part::One, is_real)
+}
 
-    position_map_from_text_lines(&lines, parse_from_char)
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec10.run(Part::Two, is_real)
 }
 
-fn calculate_from_trail_map(trail_map: &HashMap<Position, usize>, calculator: fn(Trailhead) -> usize) -> usize {
+fn calculate_from_trail_map(trail_map: &Grid<usize>, calculator: fn(Trailhead) -> usize) -> usize {
     trail_map
-        .iter()
-        .filter(|(_, h)| **h == TRAILHEAD_HEIGHT)
-        .map(|(p, _)| Trailhead::new(&trail_map, *p) )
-        .map(|th| calculator(th))
+        .positions_of(&TRAILHEAD_HEIGHT)
+        .into_iter()
+        .map(|p| Trailhead::new(trail_map, p) )
+        .map(calculator)
         .sum::<usize>()
 }
 
 const TRAILHEAD_HEIGHT: usize = 0;
 const SUMMIT_HEIGHT: usize = 9;
 
+// Every step of a hiking trail climbs by one, so all trails to a tile are equally long
+// and each of them is an optimal path of the search
 struct Trailhead {
-    tile: Tile,
+    hike: SearchResult<Position>,
+    summit_positions: Vec<Position>,
 }
 
 impl Trailhead {
-    fn new(trail_map: &HashMap<Position, usize>, position: Position) -> Self {
-        let children = Tile::populate_children(trail_map, &position, None);
-        let tile = Tile::GradualIncline(children);
+    fn new(trail_map: &Grid<usize>, position: Position) -> Self {
+        let hike = search::breadth_first_search([position], |p| uphill_positions(trail_map, p), |_| false);
+
+        let summit_positions = hike
+            .visited()
+            .filter(|p| trail_map.get(p) == Some(&SUMMIT_HEIGHT))
+            .copied()
+            .collect::<Vec<Position>>();
 
-        Self { tile, }
+        Self { hike, summit_positions }
     }
 
     fn calculate_score(&self) -> usize {
-        self.tile.summit_positions()
-            .iter()
-            .unique()
-            .count()
+        self.summit_positions.len()
     }
 
     fn calculate_rating(&self) -> usize {
-        self.tile.summit_positions()
+        self.summit_positions
             .iter()
-            .count()
+            .map(|p| self.hike.optimal_path_count(p))
+            .sum()
     }
 }
 
-enum Tile {
-    GradualIncline(Vec<Box<Tile>>),
-    Summit(Position),
-}
-
-impl Tile {
-    fn populate(
-        trail_map: &HashMap<Position, usize>, 
-        parent_position: &Position, 
-        hiking_direction: Direction,
-    ) -> Option<Self> {
-        let movement_from_parent_tile = hiking_direction.get_movement();
-        let tile_position = parent_position.new_position(&movement_from_parent_tile)?;
+fn uphill_positions(trail_map: &Grid<usize>, position: &Position) -> Vec<Position> {
+    let height = trail_map[*position];
 
-        let parent_tile_height = *trail_map.get(parent_position)?;
-        let tile_height = *trail_map.get(&tile_position)?;
-
-        if (tile_height as i32) - (parent_tile_height as i32) != 1 {
-            return None;
-        }
-
-        if tile_height == SUMMIT_HEIGHT {
-            return Some(Self::Summit(tile_position))
-        }
-
-        let banned_direction = &hiking_direction.reverse();
-        let children = Tile::populate_children(trail_map, &tile_position, Some(banned_direction));
-
-        Some(Self::GradualIncline(children))
-    }
-    
-    fn populate_children(
-        trail_map: &HashMap<Position, usize>, 
-        position: &Position,
-        banned_direction: Option<&Direction>,
-    ) -> Vec<Box<Self>> {
-        cardinal_directions()
-            .into_iter()
-            .filter(|d| {
-                match banned_direction {
-                    Some(bd) => d != bd,
-                    None => true,
-                }
-            })
-            .filter_map(|d| Tile::populate(trail_map, position, d))
-            .map(|t| Box::new(t))
-            .collect()
-    }
-
-    fn summit_positions(&self) -> Vec<Position> {
-        match self {
-            Tile::Summit(position) => vec![*position],
-            Tile::GradualIncline(child_tiles) => {
-                child_tiles
-                    .iter()
-                    .flat_map(|ct| ct.summit_positions())
-                    .collect::<Vec<Position>>()
-            },
-        }
-    }
+    trail_map
+        .neighbours(position, &cardinal_directions())
+        .filter(|(_, _, h)| **h == height + 1)
+        .map(|(_, p, _)| p)
+        .collect()
 }
 
 #[cfg(test)]
@@ -129,21 +93,21 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 36);
+        assert_eq!(run_first(false).unwrap(), 36);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 557);
+        assert_eq!(run_first(true).unwrap(), 557);
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 81);
+        assert_eq!(run_second(false).unwrap(), 81);
     }
 
     #[test]
     fn real_run_second() {
-        assert_eq!(run_second(true), 1062);
+        assert_eq!(run_second(true).unwrap(), 1062);
     }
 }
@@ -2,20 +2,58 @@ use std::{collections::HashMap, vec};
 
 use itertools::Itertools;
 
-use crate::util::read_single_string_from_file;
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::get_integers;
 
 const STONE_MULTIPLIER: usize = 2024;
 
-pub fn run_first(is_real: bool) -> usize {
-    const NUMBER_OF_BLINKS: usize = 25;
+pub struct Dec11;
 
-    let line = read_single_string_from_file(is_real, 11, None);
-    let initial_stones = get_numbers_in_string(&line);
+impl Solution for Dec11 {
+    type Input = Vec<usize>;
 
-    convert_stones(
-        initial_stones,
-        NUMBER_OF_BLINKS)
-    .len()
+    const DAY: u32 = 11;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        get_integers(text)
+    }
+
+    fn part_one(&self, initial_stones: &Self::Input) -> AocResult<Answer> {
+        const NUMBER_OF_BLINKS: usize = 25;
+
+        Ok(convert_stones(
+            initial_stones.clone(),
+            NUMBER_OF_BLINKS)
+        .len() as Answer)
+    }
+
+    fn part_two(&self, initial_stones: &Self::Input) -> AocResult<Answer> {
+        const NUMBER_OF_BLINKS: usize = 75;
+
+        let lookup_table_keys = get_lookup_table_keys(initial_stones);
+
+        let mut lookup_table: HashMap<usize, HashMap<usize, usize>> = HashMap::new();
+
+        for i in 1..NUMBER_OF_BLINKS {
+            let value_to_score_table = populate_lookup_table(
+                lookup_table_keys.clone(), 
+                i,
+                &lookup_table
+            );
+            lookup_table.insert(i, value_to_score_table);
+        }
+
+        Ok(count_stones_after_blinking_collection(initial_stones, NUMBER_OF_BLINKS, &lookup_table) as Answer)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec11.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec11.run(Part::Two, is_real)
 }
 
 fn convert_stones(mut stones: Vec<usize>, number_of_blinks: usize) -> Vec<usize> {
@@ -29,39 +67,14 @@ fn convert_stones(mut stones: Vec<usize>, number_of_blinks: usize) -> Vec<usize>
     stones
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    const NUMBER_OF_BLINKS: usize = 75;
-    
-    let line = read_single_string_from_file(is_real, 11, None);
-    let initial_stones = get_numbers_in_string(&line);
-    
-    let lookup_table_keys = get_lookup_table_keys(&initial_stones);
-
-    let mut lookup_table: HashMap<usize, HashMap<usize, usize>> = HashMap::new();
-
-    for i in 1..NUMBER_OF_BLINKS {
-        let value_to_score_table = populate_lookup_table(
-            lookup_table_keys.clone(), 
-            i,
-            &lookup_table
-        );
-        lookup_table.insert(i, value_to_score_table);
-    }
-
-    count_stones_after_blinking_collection(initial_stones, NUMBER_OF_BLINKS, &lookup_table)
-}
-
 fn get_lookup_table_keys(initial_stones: &[usize]) -> Vec<usize> {
-    let initial_stones = initial_stones
-        .iter()
-        .map(|x| *x)
-        .collect::<Vec<usize>>();
+    let initial_stones = initial_stones.to_vec();
 
     let converted_stones = convert_stones(initial_stones, 25);
     let unique_valued_stones = converted_stones
         .iter()
         .unique()
-        .map(|&s| s)
+        .copied()
         .collect::<Vec<usize>>();
     
     unique_valued_stones
@@ -85,7 +98,7 @@ fn populate_lookup_table(
 }
 
 fn count_stones_after_blinking_collection(
-    stones: Vec<usize>, 
+    stones: &[usize], 
     number_of_blinks: usize,
     lookup_table: &HashMap<usize, HashMap<usize, usize>>
 ) -> usize {
@@ -103,16 +116,6 @@ fn count_stones_after_blinking(
     StoneNode::initial(stone).count_children_recursively(number_of_blinks, lookup_table)
 }
 
-fn get_numbers_in_string(number_str: &str) -> Vec<usize> {
-    let rgx = regex::Regex::new(r"(\d+)").unwrap();
-
-    rgx
-        .find_iter(number_str)
-        .map(|m| m.as_str())
-        .filter_map(|ns| ns.parse::<usize>().ok())
-        .collect::<Vec<usize>>()
-}
-
 fn transform_stone(stone: usize) -> Vec<usize> {
     if stone == 0 {
         return vec![1];
@@ -167,8 +170,7 @@ impl StoneNode {
 
         if let Some(number_of_children) = lookup_table
             .get(&steps_until_maximum_depth)
-            .map(|lut| lut.get(&self.value))
-            .flatten() 
+            .and_then(|lut| lut.get(&self.value)) 
         {
             return *number_of_children;
         };
@@ -188,7 +190,7 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 55312);
+        assert_eq!(run_first(false).unwrap(), 55312);
     }
 
     #[test_case(12, Some(vec![1, 2]))]
@@ -204,11 +206,11 @@ mod tests {
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 233875);
+        assert_eq!(run_first(true).unwrap(), 233875);
     }
 
     #[test]
     fn real_run_second() {
-        assert_eq!(run_second(true), 277444936413293);
+        assert_eq!(run_second(true).unwrap(), 277444936413293);
     }
 }
@@ -1,122 +1,66 @@
-use std::collections::HashMap;
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::geometry::CellShape;
+use crate::util::regions::label_regions;
+use crate::util::{split_lines, Grid, Position};
 
-use itertools::Itertools;
+pub struct Dec12;
 
-use crate::util::{cardinal_directions, position_map_from_text_lines, read_from_file, Direction, Position};
+impl Solution for Dec12 {
+    type Input = Grid<char>;
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 12, None);
-    let garden_map = position_map_from_text_lines(&lines, |c| c);
+    const DAY: u32 = 12;
 
-    get_all_regions(&garden_map)
-        .iter()
-        .map(|r| r.price())
-        .sum()
-}
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        Grid::from_text_lines(&split_lines(text), Some)
+    }
 
-pub fn run_second(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 12, None);
-    let garden_map = position_map_from_text_lines(&lines, |c| c);
+    fn part_one(&self, garden_map: &Self::Input) -> AocResult<Answer> {
+        Ok(get_all_regions(garden_map)
+            .iter()
+            .map(|r| r.price())
+            .sum::<usize>() as Answer)
+    }
 
-    get_all_regions(&garden_map)
-        .iter()
-        .map(|r| r.discount_price())
-        .sum()
+    fn part_two(&self, garden_map: &Self::Input) -> AocResult<Answer> {
+        Ok(get_all_regions(garden_map)
+            .iter()
+            .map(|r| r.discount_price())
+            .sum::<usize>() as Answer)
+    }
 }
 
-fn get_all_regions(garden_map: &HashMap<Position, char>) -> Vec<Region> {
-    let unique_square_types = garden_map
-        .values()
-        .unique()
-        .map(|&p| p)
-        .collect::<Vec<char>>();
-
-    unique_square_types
-        .iter()
-        .map(|st| garden_map
-            .iter()
-            .filter(|(_, &c)| c == *st)
-            .map(|(p, _)| *p)
-            .collect::<Vec<Position>>()
-        )
-        .flat_map(|pv| split_into_regions(&pv))
-        .collect()
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec12.run(Part::One, is_real)
 }
 
-fn split_into_regions(squares: &[Position]) -> Vec<Region> {
-    find_adjoining_groups_in_square_collections(squares)
-        .iter()
-        .filter(|g| !g.is_empty())
-        .map(|g| Region { squares: g.iter().map(|p| *p).collect::<Vec<Position>>() })
-        .collect()
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec12.run(Part::Two, is_real)
 }
 
-fn find_adjoining_groups_in_square_collections(squares: &[Position]) -> Vec<Vec<Position>> {
-    let number_of_squares = squares.len();
-    let mut groups = squares
+fn get_all_regions(garden_map: &Grid<char>) -> Vec<Region> {
+    label_regions(garden_map, |a, b| a == b)
+        .regions()
         .iter()
-        .map(|s| vec![*s])
-        .collect::<Vec<Vec<Position>>>();
-
-    let mut no_change_in_last_iteration = false;
-
-    while !no_change_in_last_iteration {
-        no_change_in_last_iteration = true;
-
-        for i in 1..number_of_squares {
-            for j in 0..i {
-                let any_adjacent_squares = groups[j]
-                    .iter()
-                    .any(|g1| groups[i]
-                        .iter()
-                        .any(|g2| g1.is_adjacent_to(g2))
-                    );
-
-                if any_adjacent_squares {
-                    groups[i] = groups[i].iter().chain(groups[j].iter()).map(|p| *p).collect::<Vec<Position>>();
-                    groups[j] = Vec::new();
-
-                    no_change_in_last_iteration = false;
-                }
-                
-            }
-        }
-    }
-
-    let mut result = Vec::new();
-
-    for group in groups {
-        if !group.is_empty() {
-            result.push(group);
-        }
-    }
-
-    result
+        .map(|r| Region::new(r.cells()))
+        .collect()
 }
 
 struct Region {
-    squares: Vec<Position>,
+    shape: CellShape,
 }
 
 impl Region {
-    fn area(&self) -> usize {
-        self.squares.len()
+    fn new(squares: &[Position]) -> Self {
+        Self { shape: CellShape::from_positions(squares) }
     }
 
-    fn perimeter(&self) -> usize {
-        self.squares
-            .iter()
-            .map(|p| self.perimeter_contribution(p))
-            .sum()
+    fn area(&self) -> usize {
+        self.shape.area()
     }
 
-    fn perimeter_contribution(&self, position: &Position) -> usize {
-        let number_of_adjacent_squares = self.squares
-            .iter()
-            .filter(|s| s.is_adjacent_to(position))
-            .count();
-
-        4 - number_of_adjacent_squares
+    fn perimeter(&self) -> usize {
+        self.shape.perimeter()
     }
 
     fn price(&self) -> usize {
@@ -124,54 +68,7 @@ impl Region {
     }
 
     fn number_of_sides(&self) -> usize {
-        let all_side_contributions = self.squares
-            .iter()
-            .flat_map(|s| self.side_contributions(s))
-            .collect::<Vec<SideContribution>>();
-
-        let columns = self.squares
-            .iter()
-            .map(|s| s.x)
-            .unique()
-            .collect::<Vec<usize>>();
-        let rows = self.squares
-            .iter()
-            .map(|s| s.y)
-            .unique()
-            .collect::<Vec<usize>>();
-
-        let mut number_of_sides = 0;
-
-        for column in columns {
-            for direction in vec![Direction::W, Direction::E] {
-                let sides = Side::from_side_contributions(&all_side_contributions, column, &direction);
-                number_of_sides = number_of_sides + sides.len();
-            }
-        }
-        
-        for row in rows {
-            for direction in vec![Direction::N, Direction::S] {
-                let sides = Side::from_side_contributions(&all_side_contributions, row, &direction);
-                number_of_sides = number_of_sides + sides.len();
-            }
-        }
-
-        number_of_sides
-    }
-
-    fn side_contributions(&self, position: &Position) -> Vec<SideContribution> {
-        cardinal_directions()
-            .iter()
-            .filter_map(|d| {
-                if let Some(adjacent_square) = position.walk_in_direction(d) {
-                    if self.squares.contains(&adjacent_square) {
-                        return None;
-                    }
-                }
-
-                return Some(SideContribution { position: *position, side: *d });
-            })
-            .collect()
+        self.shape.number_of_sides()
     }
 
     fn discount_price(&self) -> usize {
@@ -179,49 +76,13 @@ impl Region {
     }
 }
 
-struct Side {
-    positions: Vec<Position>,
-    direction: Direction,
-}
-
-impl Side {
-    fn from_side_contributions(
-        side_contributions: &[SideContribution],
-        order: usize,
-        direction: &Direction,
-    ) -> Vec<Side> {
-        let position_filtering_closure: Box<dyn Fn(Position) -> bool> = match direction {
-            Direction::N | Direction::S => Box::new(|p: Position| p.y == order),
-            Direction::E | Direction::W => Box::new(|p: Position| p.x == order),
-            _ => panic!("no"),
-        };
-
-        let matching_side_contribution_positions = side_contributions
-            .iter()
-            .filter(|sc| sc.side == *direction && position_filtering_closure(sc.position))
-            .map(|sc| sc.position)
-            .collect::<Vec<Position>>();
-        
-        find_adjoining_groups_in_square_collections(&matching_side_contribution_positions)
-            .iter()
-            .map(|g| Side { positions: g.to_vec(), direction: *direction })
-            .collect::<Vec<Side>>()
-    }
-}
-
-#[derive(Debug)]
-struct SideContribution {
-    position: Position,
-    side: Direction,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 1930);
+        assert_eq!(run_first(false).unwrap(), 1930);
     }
 
     #[test]
@@ -234,7 +95,7 @@ mod tests {
             "OOOOO".to_string(),
         ];
 
-        let garden_map = position_map_from_text_lines(&lines, |c| c);
+        let garden_map = Grid::from_text_lines(&lines, Some).unwrap();
 
         let combined_price = get_all_regions(&garden_map)
             .iter()
@@ -246,17 +107,17 @@ mod tests {
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 1546338);
+        assert_eq!(run_first(true).unwrap(), 1546338);
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 1206);
+        assert_eq!(run_second(false).unwrap(), 1206);
     }
 
     #[test]
     fn test_number_of_sides() {
-        let region = Region { squares: vec![Position { x: 0, y: 0 }] };
+        let region = Region::new(&[Position { x: 0, y: 0 }]);
         let result = region.number_of_sides();
 
         assert_eq!(result, 4);
@@ -264,6 +125,6 @@ mod tests {
 
     #[test]
     fn real_run_second() {
-        assert_eq!(run_second(true), 978590);
+        assert_eq!(run_second(true).unwrap(), 978590);
     }
 }
@@ -1,50 +1,58 @@
-use crate::util::{read_from_file, Position};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::parsing::LinePattern;
+use crate::util::sections::split_sections;
+use crate::util::{Point, Vector};
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 13, None);
+pub struct Dec13;
 
-    parse_prize_recipes(lines)
-        .iter()
-        .filter_map(|pr| pr.get_optimal_attempt(0))
-        .map(|a| a.tokens())
-        .sum()
-}
-
-pub fn run_second(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 13, None);
+impl Solution for Dec13 {
+    type Input = Vec<PrizeRecipe>;
 
-    parse_prize_recipes(lines)
-        .iter()
-        .filter_map(|pr| pr.get_optimal_attempt(10000000000000))
-        .map(|a| a.tokens())
-        .sum()
-}
+    const DAY: u32 = 13;
 
-fn parse_prize_recipes(lines: Vec<String>) -> Vec<PrizeRecipe> {
-    let mut line_iterator = lines.iter().peekable();
-    let mut prize_recipes = Vec::new();
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        split_sections(text)
+            .iter()
+            .map(|s| s.parse(parse_prize_recipe))
+            .collect()
+    }
 
-    loop {
-        let prize_recipe = match parse_prize_recipe(&mut line_iterator) {
-            Some(pr) => pr,
-            None => break,
-        };
-        prize_recipes.push(prize_recipe);
+    fn part_one(&self, prize_recipes: &Self::Input) -> AocResult<Answer> {
+        Ok(prize_recipes
+            .iter()
+            .filter_map(|pr| pr.get_optimal_attempt(0))
+            .map(|a| a.tokens())
+            .sum::<usize>() as Answer)
+    }
 
-        if let None = line_iterator.next() {
-            break;
-        }
+    fn part_two(&self, prize_recipes: &Self::Input) -> AocResult<Answer> {
+        Ok(prize_recipes
+            .iter()
+            .filter_map(|pr| pr.get_optimal_attempt(10000000000000))
+            .map(|a| a.tokens())
+            .sum::<usize>() as Answer)
     }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec13.run(Part::One, is_real)
+}
 
-    prize_recipes
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec13.run(Part::Two, is_real)
 }
 
-fn parse_prize_recipe<'a>(i_tmp: &mut impl Iterator<Item = &'a String>) -> Option<PrizeRecipe> {
-    let a_movement = parse_button_line(i_tmp.next()?)?;
-    let b_movement = parse_button_line(i_tmp.next()?)?;
-    let prize_position = parse_prize_position(i_tmp.next()?)?;
+fn parse_prize_recipe(lines: &[&str]) -> AocResult<PrizeRecipe> {
+    let [a_line, b_line, prize_line] = lines else {
+        return Err(AocError::parse("Expected a prize recipe of two button lines and a prize line"));
+    };
+
+    let a_movement = parse_button_line(a_line).map_err(|e| e.at_line(0, a_line))?;
+    let b_movement = parse_button_line(b_line).map_err(|e| e.at_line(1, b_line))?;
+    let prize_position = parse_prize_position(prize_line).map_err(|e| e.at_line(2, prize_line))?;
 
-    Some(PrizeRecipe { a_movement, b_movement, prize_position })
+    Ok(PrizeRecipe { a_movement, b_movement, prize_position })
 }
 
 struct Attempt {
@@ -59,22 +67,23 @@ impl Attempt {
 }
 
 #[derive(Debug)]
-struct ButtonMovement {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug)]
-struct PrizeRecipe {
-    a_movement: ButtonMovement,
-    b_movement: ButtonMovement,
-    prize_position: Position,
+pub struct PrizeRecipe {
+    a_movement: Vector<i64>,
+    b_movement: Vector<i64>,
+    prize_position: Point<i64>,
 }
 
 impl PrizeRecipe {
-    fn get_optimal_attempt(&self, prize_position_shift: usize) -> Option<Attempt> {
-        let px = vec![self.a_movement.x, self.b_movement.x, prize_position_shift + self.prize_position.x];
-        let py = vec![self.a_movement.y, self.b_movement.y, prize_position_shift + self.prize_position.y];
+    fn get_optimal_attempt(&self, prize_position_shift: i64) -> Option<Attempt> {
+        let prize_position = self
+            .prize_position
+            .checked_add(&Vector::new(prize_position_shift, prize_position_shift))?
+            .try_convert::<usize>()?;
+        let a_movement = self.a_movement.try_convert::<usize>()?;
+        let b_movement = self.b_movement.try_convert::<usize>()?;
+
+        let px = vec![a_movement.dx, b_movement.dx, prize_position.x];
+        let py = vec![a_movement.dy, b_movement.dy, prize_position.y];
 
         let (a, b) = solve_linear_system(px, py)?;
 
@@ -82,24 +91,19 @@ impl PrizeRecipe {
     }
 }
 
-fn parse_button_line(line: &str) -> Option<ButtonMovement> {
-    let rgx = regex::Regex::new(r"Button [AB]: X\+(\d+), Y\+(\d+)").ok()?;
-    let (_, [x, y]) = rgx.captures(line)?.extract();
+static BUTTON_LINE: LinePattern = LinePattern::described_as("Button {}: X+{}, Y+{}", "a button line like Button A: X+94, Y+34");
+static PRIZE_LINE: LinePattern = LinePattern::described_as("Prize: X={}, Y={}", "a prize line like Prize: X=8400, Y=5400");
 
-    let x = x.parse::<usize>().ok()?;
-    let y = y.parse::<usize>().ok()?;
+fn parse_button_line(line: &str) -> AocResult<Vector<i64>> {
+    let (_, dx, dy) = BUTTON_LINE.parse::<(char, i64, i64)>(line)?;
 
-    Some(ButtonMovement { x, y })
+    Ok(Vector::new(dx, dy))
 }
 
-fn parse_prize_position(line: &str) -> Option<Position> {
-    let rgx = regex::Regex::new(r"Prize: X=(\d+), Y=(\d+)").ok()?;
-    let (_, [x, y]) = rgx.captures(line)?.extract();
+fn parse_prize_position(line: &str) -> AocResult<Point<i64>> {
+    let (x, y) = PRIZE_LINE.parse::<(i64, i64)>(line)?;
 
-    let x = x.parse::<usize>().ok()?;
-    let y = y.parse::<usize>().ok()?;
-
-    Some(Position { x, y })
+    Ok(Point::new(x, y))
 }
 
 fn solve_linear_system(p1: Vec<usize>, p2: Vec<usize>) -> Option<(usize, usize)> {
@@ -131,7 +135,7 @@ fn solve_linear_system(p1: Vec<usize>, p2: Vec<usize>) -> Option<(usize, usize)>
 
     let p1_hat_2 = p1[2] - p1_hat_1;
 
-    if p1_hat_2 % p1[0] != 0 {
+    if !p1_hat_2.is_multiple_of(p1[0]) {
         return None;
     }
 
@@ -143,6 +147,7 @@ fn solve_linear_system(p1: Vec<usize>, p2: Vec<usize>) -> Option<(usize, usize)>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ParseError;
     use test_case::test_case;
 
     #[test_case(vec![94, 22, 8400], vec![34, 67, 5400], Some((80, 40)))]
@@ -157,21 +162,32 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 480);
+        assert_eq!(run_first(false).unwrap(), 480);
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 29517);
+        assert_eq!(run_first(true).unwrap(), 29517);
+    }
+
+    #[test]
+    fn test_parse_reports_recipe_and_line() {
+        let text = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748 Y=12176";
+
+        assert!(matches!(
+            Dec13.parse(text),
+            Err(AocError::Parse(ParseError { line_number: Some(7), section: Some(2), .. }))
+        ));
+        assert!(Dec13.parse("Button A: X+94, Y+34\nPrize: X=8400, Y=5400").is_err());
     }
 
     // #[test]
     // fn test_run_second() {
-    //     assert_eq!(run_second(false), xx);
+    //     assert_eq!(run_second(false).unwrap(), xx);
     // }
 
     // #[test]
     // fn real_run_second() {
-    //     assert_eq!(run_second(true), xx);
+    //     assert_eq!(run_second(true).unwrap(), xx);
     // }
 }
@@ -1,61 +1,122 @@
-use crate::util::{read_from_file, Dimensions, Position, Separation};
+use crate::error::{parse_non_empty_lines, AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::torus::{Particle, ParticleSimulation, Torus};
+use crate::util::parsing::LinePattern;
+use crate::util::{Dimensions, Position, Vector};
 
-pub fn run_first(is_real: bool) -> usize {
-    let map_dimensions: Dimensions = match is_real { 
-        true => Dimensions { width: 101, height: 103 },
-        false => Dimensions { width: 11, height: 7},
-    };
+pub struct Dec14;
+
+impl Solution for Dec14 {
+    type Input = Floor;
+
+    const DAY: u32 = 14;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let mut dimensions = None;
+        let mut robots = Vec::new();
+
+        for floor_line in parse_non_empty_lines(text, parse_floor_line)? {
+            match (floor_line, dimensions) {
+                (FloorLine::Size(_), Some(_)) => return Err(AocError::parse("The floor size is given more than once")),
+                (FloorLine::Size(size), None) => dimensions = Some(size),
+                (FloorLine::Robot(robot), _) => robots.push(robot),
+            }
+        }
+
+        Ok(Floor { dimensions: dimensions.unwrap_or(REAL_FLOOR), robots })
+    }
+
+    fn part_one(&self, floor: &Self::Input) -> AocResult<Answer> {
+        safety_factor(&floor.robots, &floor.dimensions)
+    }
+
+    fn part_two(&self, _floor: &Self::Input) -> AocResult<Answer> {
+        Ok(0)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec14.run(Part::One, is_real)
+}
+
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec14.run(Part::Two, is_real)
+}
+
+// Real inputs are only robots and always played out on this floor. Anything else, like the
+// example, starts with a line such as floor=11x7.
+const REAL_FLOOR: Dimensions = Dimensions { width: 101, height: 103 };
+
+pub struct Floor {
+    dimensions: Dimensions,
+    robots: Vec<Robot>,
+}
+
+enum FloorLine {
+    Size(Dimensions),
+    Robot(Robot),
+}
+
+static FLOOR_SIZE: LinePattern = LinePattern::described_as("floor={}x{}", "a floor size like floor=11x7");
+
+fn parse_floor_line(line: &str) -> AocResult<FloorLine> {
+    match FLOOR_SIZE.matches(line) {
+        true => {
+            let (width, height) = FLOOR_SIZE.parse::<(usize, usize)>(line)?;
+
+            Ok(FloorLine::Size(Dimensions { width, height }))
+        },
+        false => Ok(FloorLine::Robot(parse_robot_from_line(line)?)),
+    }
+}
+
+fn safety_factor(robots: &[Robot], floor_dimensions: &Dimensions) -> AocResult<Answer> {
     const NUMBER_OF_MOVEMENTS: usize = 100;
 
-    let lines = read_from_file(is_real, 14, None);
-    let moved_robots = lines
-        .iter()
-        .filter_map(|l| parse_robot_from_line(l))
-        .map(|r| r.move_wrapping_around(&map_dimensions, NUMBER_OF_MOVEMENTS))
-        .collect::<Vec<Robot>>();
+    let floor = Torus::new(floor_dimensions.width, floor_dimensions.height)
+        .ok_or(AocError::unsolvable("The floor has no tiles"))?;
+
+    if let Some(robot) = robots.iter().find(|r| !floor_dimensions.includes(&r.position)) {
+        return Err(AocError::unsolvable(format!(
+            "Robot at {},{} is outside the {}x{} floor",
+            robot.position.x,
+            robot.position.y,
+            floor_dimensions.width,
+            floor_dimensions.height,
+        )));
+    }
 
-    let quadrants = Quadrant::from_dimensions(&map_dimensions).unwrap();
-    quadrants
+    let particles = robots.iter().map(|r| r.as_particle()).collect();
+    let moved_robot_positions = ParticleSimulation::new(floor, particles).positions_after(NUMBER_OF_MOVEMENTS);
+
+    let quadrants = Quadrant::from_dimensions(floor_dimensions)
+        .ok_or(AocError::unsolvable("The floor cannot be split into quadrants"))?;
+
+    Ok(quadrants
         .iter()
         .map(|q| {
-            moved_robots.iter().filter(|mr| q.includes(&mr.position)).count()
+            moved_robot_positions.iter().filter(|p| q.includes(p)).count()
         })
-        .fold(1, |a, qc| a*qc)
+        .product::<usize>() as Answer)
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    0
-}
-
-struct Robot {
+pub struct Robot {
     position: Position,
-    velocity: Separation,
+    velocity: Vector<i64>,
 }
 
 impl Robot {
-    fn move_wrapping_around(self, dimensions: &Dimensions, number_of_movements: usize) -> Self {
-        let position = self.position.move_wrapping_around(
-            &self.velocity.multiply(number_of_movements), 
-            dimensions
-        );
-
-        Robot { position, velocity: self.velocity }
+    fn as_particle(&self) -> Particle {
+        Particle { position: self.position, velocity: self.velocity }
     }
 }
 
-fn parse_robot_from_line(line: &str) -> Option<Robot> {
-    let rgx = regex::Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").ok()?;
-    let (_, [x, y, dx, dy]) = rgx.captures(line)?.extract();
+static ROBOT: LinePattern = LinePattern::described_as("p={},{} v={},{}", "a robot like p=0,4 v=3,-3");
 
-    let x = x.parse::<usize>().ok()?;
-    let y = y.parse::<usize>().ok()?;
-    let dx = dx.parse::<i32>().ok()?;
-    let dy = dy.parse::<i32>().ok()?;
+fn parse_robot_from_line(line: &str) -> AocResult<Robot> {
+    let (x, y, dx, dy) = ROBOT.parse::<(usize, usize, i64, i64)>(line)?;
 
-    let position = Position { x, y };
-    let velocity = Separation { dx, dy };
-
-    Some(Robot { position, velocity })
+    Ok(Robot { position: Position { x, y }, velocity: Vector::new(dx, dy) })
 }
 
 struct Quadrant {
@@ -65,7 +126,7 @@ struct Quadrant {
 
 impl Quadrant {
     fn includes(&self, position: &Position) -> bool {
-        return self.upper_left.x <= position.x 
+        self.upper_left.x <= position.x 
             && self.upper_left.y <= position.y
             && position.x <= self.lower_right.x
             && position.y <= self.lower_right.y
@@ -74,7 +135,7 @@ impl Quadrant {
 
 impl Quadrant {
     fn from_dimensions(dimensions: &Dimensions) -> Option<Vec<Quadrant>> {
-        if dimensions.width % 2 == 0 || dimensions.height % 2 == 0 {
+        if dimensions.width.is_multiple_of(2) || dimensions.height.is_multiple_of(2) {
             return None;
         }
 
@@ -111,21 +172,29 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 12);
+        assert_eq!(run_first(false).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_floor_size_comes_from_the_input() {
+        assert_eq!(Dec14.parse("floor=11x7\np=0,4 v=3,-3").unwrap().dimensions, Dimensions { width: 11, height: 7 });
+        assert_eq!(Dec14.parse("p=0,4 v=3,-3\n").unwrap().dimensions, REAL_FLOOR);
+        assert!(Dec14.parse("floor=11x7\nfloor=11x7").is_err());
+        assert!(Dec14.solve(Part::One, "floor=11x7\np=20,5 v=1,1").is_err());
     }
 
     // #[test]
     // fn real_run_first() {
-    //     assert_eq!(run_first(true), xx);
+    //     assert_eq!(run_first(true).unwrap(), xx);
     // }
 
     // #[test]
     // fn test_run_second() {
-    //     assert_eq!(run_second(false), xx);
+    //     assert_eq!(run_second(false).unwrap(), xx);
     // }
 
     // #[test]
     // fn real_run_second() {
-    //     assert_eq!(run_second(true), xx);
+    //     assert_eq!(run_second(true).unwrap(), xx);
     // }
 }
@@ -1,90 +1,117 @@
-use std::collections::HashMap;
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::sections::split_fixed_sections;
+use crate::util::{Direction, Grid};
 
-use crate::util::{position_map_from_text_lines, read_from_file, Direction, Position};
+pub struct Dec15;
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 15, None);
+impl Solution for Dec15 {
+    type Input = (Grid<Location>, Vec<Direction>);
 
-    let mut map_lines = Vec::new();
-    let mut line_iterator = lines.into_iter().peekable();
+    const DAY: u32 = 15;
 
-    while is_map_line(line_iterator.peek()) {
-        map_lines.push(line_iterator.next().unwrap());
-    }
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let [map_section, move_section] = split_fixed_sections(text)?;
+
+        let warehouse_map = map_section.parse(|lines| {
+            let warehouse_map = Grid::from_text_lines(lines, parse_location_from_char)?;
+
+            match warehouse_map.find(|l| *l == Location::Robot) {
+                Some(_) => Ok(warehouse_map),
+                None => Err(AocError::parse("No robot found in the warehouse")),
+            }
+        })?;
 
-    let warehouse_map = position_map_from_text_lines(&map_lines, |c| parse_location_from_char(c));
+        let directions = move_section
+            .parse_lines(parse_directions_from_line)?
+            .into_iter()
+            .flatten()
+            .collect();
 
-    _ = line_iterator.next();
+        Ok((warehouse_map, directions))
+    }
 
-    let directions = line_iterator
-        .flat_map(|l| l.chars().collect::<Vec<char>>())
-        .filter_map(|c| Direction::from_char(c))
-        .collect::<Vec<Direction>>();
+    fn part_one(&self, (warehouse_map, directions): &Self::Input) -> AocResult<Answer> {
+        let warehouse_map = directions
+            .iter()
+            .try_fold(warehouse_map.clone(), |map_before, d| move_robot_in_direction(map_before, *d))?;
 
-    let warehouse_map = directions
-        .into_iter()
-        .fold(warehouse_map, |map_before, d| move_robot_in_direction(map_before, d));
+        Ok(warehouse_map
+            .positions_of(&Location::Crate)
+            .iter()
+            .map(|p| p.x + 100 * p.y)
+            .sum::<usize>() as Answer)
+    }
 
-    warehouse_map
-        .iter()
-        .filter(|(_, l)| l == &&Location::Crate)
-        .map(|(p, _)| p.x + 100 * p.y)
-        .sum::<usize>()
+    fn part_two(&self, _input: &Self::Input) -> AocResult<Answer> {
+        Ok(0)
+    }
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    0
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec15.run(Part::One, is_real)
 }
 
-fn is_map_line(maybe_line: Option<&String>) -> bool {
-    let line = match maybe_line {
-        Some(line) => line,
-        None => return false,
-    };
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec15.run(Part::Two, is_real)
+}
 
-    match line.chars().next() {
-        Some(first_char) => first_char == '#',
-        None => false,
-    }
+fn parse_directions_from_line(line: &str) -> AocResult<Vec<Direction>> {
+    line
+        .chars()
+        .enumerate()
+        .map(|(column, c)| {
+            Direction::from_char(c).ok_or(AocError::parse(format!("Unexpected move '{}'", c)).at_column(column))
+        })
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Location {
+pub enum Location {
     Obstruction,
     Crate,
     Robot,
     Free,
 }
 
-fn parse_location_from_char(c: char) -> Location {
+fn parse_location_from_char(c: char) -> Option<Location> {
     match c {
-        '#' => Location::Obstruction,
-        'O' => Location::Crate,
-        '@' => Location::Robot,
-        '.' => Location::Free,
-        _ => panic!("Unexpected character"),
+        '#' => Some(Location::Obstruction),
+        'O' => Some(Location::Crate),
+        '@' => Some(Location::Robot),
+        '.' => Some(Location::Free),
+        _ => None,
     }
 }
 
 fn move_robot_in_direction(
-    mut warehouse_map: HashMap<Position, Location>,
+    mut warehouse_map: Grid<Location>,
     direction: Direction,
-) -> HashMap<Position, Location> {
+) -> AocResult<Grid<Location>> {
     let initial_robot_position = warehouse_map
-        .iter()
-        .find(|(_p, l)| *l == &Location::Robot)
-        .map(|(p, _)| p.clone()).unwrap();
+        .find(|l| *l == Location::Robot)
+        .ok_or(AocError::unsolvable("No robot found in the warehouse"))?;
 
     let mut last_position = initial_robot_position;
     let mut last_location = Location::Robot;
     let mut modified_locations = Vec::new();
 
     loop {
-        let end_position = last_position.walk_in_direction(&direction).unwrap();
-        let end_location = warehouse_map.get(&end_position).unwrap().clone();
+        // Real warehouses are walled in, without walls the robot or a crate can be pushed off the map
+        let end_location = last_position
+            .walk_in_direction(&direction)
+            .and_then(|p| warehouse_map.get(&p).map(|l| (p, *l)));
+
+        let Some((end_position, end_location)) = end_location else {
+            return Err(AocError::unsolvable(format!(
+                "Pushed off the warehouse map at {},{}",
+                last_position.x,
+                last_position.y,
+            )));
+        };
 
         if end_location == Location::Obstruction {
-            return warehouse_map;
+            return Ok(warehouse_map);
         }
 
         modified_locations.push((end_position, last_location));
@@ -100,17 +127,17 @@ fn move_robot_in_direction(
     modified_locations.push((initial_robot_position, Location::Free));
 
     for (position, location) in modified_locations {
-        warehouse_map.insert(position, location);
+        warehouse_map.set(&position, location);
     }
 
     // if the location into which the robot is moving is an obstruction, stop and return the same map
     // if the location into which the robot is moving is free, move there and stop
     // if the location into which the robot is moving has a crate, check the next one, until we run into either a free location or an obstruction
     
-    warehouse_map
+    Ok(warehouse_map)
 }
 
-// fn warehouse_map_has_crate(warehouse_map: &HashMap<Position, Location>, position: &Position) -> bool {
+// fn warehouse_map_has_crate(warehouse_map: &Grid<Location>, position: &Position) -> bool {
 //     if let Some(location) = warehouse_map.get(position) {
 //         return location == &Location::Crate;
 //     };
@@ -121,102 +148,93 @@ fn move_robot_in_direction(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ParseError;
+    use crate::util::split_lines;
+    use test_case::test_case;
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 10092);
+        assert_eq!(run_first(false).unwrap(), 10092);
+    }
+
+    #[test]
+    fn test_parse_reports_unexpected_move() {
+        let result = Dec15.parse("####\n#@O#\n####\n\n<>\n^x");
+
+        assert!(matches!(
+            result, 
+            Err(AocError::Parse(ParseError { line_number: Some(6), column: Some(2), section: Some(2), .. }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_letter_moves() {
+        let result = Dec15.parse("####\n#@O#\n####\n\n<>\nU");
+
+        assert!(matches!(
+            result,
+            Err(AocError::Parse(ParseError { line_number: Some(6), column: Some(1), .. }))
+        ));
+    }
+
+    fn warehouse(text: &str) -> Grid<Location> {
+        Grid::from_text_lines(&split_lines(text), parse_location_from_char).unwrap()
     }
 
     #[test]
     fn test_move_robot() {
-        let warehouse_map = vec![
-            (Position { x: 0, y: 0 }, Location::Obstruction),
-            (Position { x: 1, y: 0 }, Location::Free),
-            (Position { x: 2, y: 0 }, Location::Robot),
-        ].into_iter().collect::<HashMap<Position, Location>>();
-        let modified_warehouse_map = move_robot_in_direction(warehouse_map, Direction::W);
+        let modified_warehouse_map = move_robot_in_direction(warehouse("#.@"), Direction::W).unwrap();
 
-        assert_eq!(modified_warehouse_map.get(&Position { x: 0, y: 0 }).unwrap(), &Location::Obstruction);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 0 }).unwrap(), &Location::Robot);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 2, y: 0 }).unwrap(), &Location::Free);
+        assert_eq!(modified_warehouse_map, warehouse("#@."));
     }
 
     #[test]
     fn test_robot_moves_a_single_crate() {
-        let warehouse_map = vec![
-            (Position { x: 0, y: 0 }, Location::Robot),
-            (Position { x: 1, y: 0 }, Location::Crate),
-            (Position { x: 2, y: 0 }, Location::Free),
-            (Position { x: 3, y: 0 }, Location::Obstruction),
-        ].into_iter().collect::<HashMap<Position, Location>>();
-        let modified_warehouse_map = move_robot_in_direction(warehouse_map, Direction::E);
+        let modified_warehouse_map = move_robot_in_direction(warehouse("@O.#"), Direction::E).unwrap();
 
-        assert_eq!(modified_warehouse_map.get(&Position { x: 0, y: 0 }).unwrap(), &Location::Free);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 0 }).unwrap(), &Location::Robot);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 2, y: 0 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 3, y: 0 }).unwrap(), &Location::Obstruction);
+        assert_eq!(modified_warehouse_map, warehouse(".@O#"));
     }
 
     #[test]
     fn test_robot_moves_multiple_crates() {
-        let warehouse_map = vec![
-            (Position { x: 1, y: 0 }, Location::Obstruction),
-            (Position { x: 1, y: 1 }, Location::Free),
-            (Position { x: 1, y: 2 }, Location::Crate),
-            (Position { x: 1, y: 3 }, Location::Crate),
-            (Position { x: 1, y: 4 }, Location::Robot),
-        ].into_iter().collect::<HashMap<Position, Location>>();
-        let modified_warehouse_map = move_robot_in_direction(warehouse_map, Direction::N);
-
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 0 }).unwrap(), &Location::Obstruction);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 1 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 2 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 3 }).unwrap(), &Location::Robot);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 4 }).unwrap(), &Location::Free);
+        let modified_warehouse_map = move_robot_in_direction(warehouse("##\n#.\n#O\n#O\n#@"), Direction::N).unwrap();
+
+        assert_eq!(modified_warehouse_map, warehouse("##\n#O\n#O\n#@\n#."));
     }
 
     #[test]
     fn test_robot_blocked_by_obstruction() {
-        let warehouse_map = vec![
-            (Position { x: 5, y: 4 }, Location::Robot),
-            (Position { x: 5, y: 5 }, Location::Obstruction),
-        ].into_iter().collect::<HashMap<Position, Location>>();
-        let modified_warehouse_map = move_robot_in_direction(warehouse_map, Direction::S);
+        let modified_warehouse_map = move_robot_in_direction(warehouse(".@\n.#"), Direction::S).unwrap();
 
-        assert_eq!(modified_warehouse_map.get(&Position { x: 5, y: 4 }).unwrap(), &Location::Robot);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 5, y: 5 }).unwrap(), &Location::Obstruction);
+        assert_eq!(modified_warehouse_map, warehouse(".@\n.#"));
     }
 
     #[test]
     fn test_robot_and_crates_blocked_by_obstruction() {
-        let warehouse_map = vec![
-            (Position { x: 0, y: 0 }, Location::Obstruction),
-            (Position { x: 1, y: 0 }, Location::Crate),
-            (Position { x: 2, y: 0 }, Location::Crate),
-            (Position { x: 3, y: 0 }, Location::Crate),
-            (Position { x: 4, y: 0 }, Location::Robot),
-        ].into_iter().collect::<HashMap<Position, Location>>();
-        let modified_warehouse_map = move_robot_in_direction(warehouse_map, Direction::W);
-
-        assert_eq!(modified_warehouse_map.get(&Position { x: 0, y: 0 }).unwrap(), &Location::Obstruction);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 1, y: 0 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 2, y: 0 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 3, y: 0 }).unwrap(), &Location::Crate);
-        assert_eq!(modified_warehouse_map.get(&Position { x: 4, y: 0 }).unwrap(), &Location::Robot);
+        let modified_warehouse_map = move_robot_in_direction(warehouse("#OOO@"), Direction::W).unwrap();
+
+        assert_eq!(modified_warehouse_map, warehouse("#OOO@"));
+    }
+
+    #[test_case("@O", Direction::E)]
+    #[test_case(".@", Direction::N)]
+    #[test_case("..", Direction::W)]
+    fn test_unwalled_warehouse_is_unsolvable(text: &str, direction: Direction) {
+        assert!(matches!(move_robot_in_direction(warehouse(text), direction), Err(AocError::Unsolvable(_))));
     }
 
     #[test]
     fn real_run_first() {
-        assert_eq!(run_first(true), 1486930);
+        assert_eq!(run_first(true).unwrap(), 1486930);
     }
 
     #[test]
     fn test_run_second() {
-        assert_eq!(run_second(false), 9021);
+        assert_eq!(run_second(false).unwrap(), 9021);
     }
 
     // #[test]
     // fn real_run_second() {
-    //     assert_eq!(run_second(true), xx);
+    //     assert_eq!(run_second(true).unwrap(), xx);
     // }
 }
@@ -1,274 +1,110 @@
-use std::{collections::HashMap, usize};
+use crate::error::{AocError, AocResult};
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::render::{Canvas, CellStyle, Colour};
+use crate::util::search::{self, SearchResult};
+use crate::util::{split_lines, Direction, Grid, Position};
 
-use itertools::Itertools;
+pub struct Dec16;
 
-use crate::util::{cardinal_directions, position_map_from_text_lines, read_from_file, Direction, Position};
+impl Solution for Dec16 {
+    type Input = Grid<Location>;
 
-pub fn run_first(is_real: bool) -> usize {
-    let lines = read_from_file(is_real, 16, None);
+    const DAY: u32 = 16;
 
-    let maze_map = position_map_from_text_lines(&lines, |c| Location::from_char(c));
-    
-    let intersections = intersections_from_maze_map(&maze_map);
-    let wormholes = wormholes_from_intersections(&maze_map, &intersections);
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        let maze_map = Grid::from_text_lines(&split_lines(text), Location::from_char)?;
 
-    // println!("Intersections: {}", intersections.len());
-    // println!("Wormholes: {}", wormholes.len());
-
-    // let positions_in_wormhole_path = find_positions_in_wormhole_path(&intersections, &wormholes);
-    
-    // println!("Positions in wormhole path: {}", positions_in_wormhole_path.len());
-    
-    // let intersections = remove_redundant_intersections(intersections, &positions_in_wormhole_path);
-    // let wormholes = shorten_wormholes(&maze_map, wormholes, &positions_in_wormhole_path);
-
-    // println!("Intersections: {}", intersections.len());
-    // println!("Wormholes: {}", wormholes.len());
-
-    // let positions_in_wormhole_path = find_positions_in_wormhole_path(&intersections, &wormholes);
-    
-    // println!("Positions in wormhole path: {}", positions_in_wormhole_path.len());
-    
-    // let intersections = remove_redundant_intersections(intersections, &positions_in_wormhole_path);
-    // let wormholes = shorten_wormholes(&maze_map, wormholes, &positions_in_wormhole_path);
-
-    // println!("Intersections: {}", intersections.len());
-    // println!("Wormholes: {}", wormholes.len());
-
-    // let positions_in_wormhole_path = find_positions_in_wormhole_path(&intersections, &wormholes);
-    
-    // println!("Positions in wormhole path: {}", positions_in_wormhole_path.len());
-    
-    // let intersections = remove_redundant_intersections(intersections, &positions_in_wormhole_path);
-    // let wormholes = shorten_wormholes(&maze_map, wormholes, &positions_in_wormhole_path);
-
-    println!("Intersections: {}", intersections.len());
-    println!("Wormholes: {}", wormholes.len());
-
-    // todo: find wormhole pairs with same start and stop positions, take the better pair
-    
-    let start_position = maze_map.iter().find(|(_, l)| l.is_start_tile()).map(|(p, _)| *p).unwrap();
-    let initial_node = IntersectionNode::new(&start_position);
-
-    initial_node.get_optimal_cost(&maze_map, &intersections, &wormholes).unwrap()
-
-    // let path_tree = PathNode2::new(start_position);
+        if maze_map.find(|l| l.is_start_tile()).is_none() {
+            return Err(AocError::parse("No start tile found in the maze"));
+        }
 
-    // // todo: list of locations of intersections
-    // // todo: use the list and the maze map to define wormholes
+        Ok(maze_map)
+    }
 
-    // path_tree.get_optimal_cost(&maze_map, &wormholes).unwrap()
-    // 0
-}
+    fn part_one(&self, maze_map: &Self::Input) -> AocResult<Answer> {
+        find_optimal_routes(maze_map)
+            .cost()
+            .map(|c| c as Answer)
+            .ok_or(AocError::unsolvable("There is no path from the start tile to the end tile"))
+    }
 
-fn intersections_from_maze_map(maze_map: &HashMap<Position, Location>) -> Vec<Intersection> {
-    maze_map
-        .iter()
-        .filter(|(_, l)| l.is_free())
-        .filter_map(|(p, _)| get_intersection(&maze_map, p))
-        .collect::<Vec<Intersection>>()
+    fn part_two(&self, _maze_map: &Self::Input) -> AocResult<Answer> {
+        Ok(0)
+    }
 }
 
-fn wormholes_from_intersections(maze_map: &HashMap<Position, Location>, intersections: &[Intersection]) -> Vec<Wormhole> {
-    intersections
-        .iter()
-        .flat_map(|i| i.get_possible_states())
-        .filter_map(|rs| Wormhole::from_state(rs, &maze_map, &intersections))
-        .collect::<Vec<Wormhole>>()
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    Dec16.run(Part::One, is_real)
 }
 
-fn find_positions_in_wormhole_path(intersections: &[Intersection], wormholes: &[Wormhole]) -> Vec<Position> {
-    intersections
-        .iter()
-        .filter(|i| wormholes
-            .iter()
-            .filter(|w| w.one.position == i.position)
-            .count() == 2
-        ).map(|i| i.position)
-        .collect::<Vec<Position>>()
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    Dec16.run(Part::Two, is_real)
 }
 
-fn remove_redundant_intersections(intersections: Vec<Intersection>, positions: &[Position]) -> Vec<Intersection> {
-    intersections
-        .into_iter()
-        .filter(|i| !positions.contains(&i.position))
-        .collect::<Vec<Intersection>>()
-}
+// The maze with one of the cheapest routes drawn over it, from the start tile to the end tile
+pub fn render_optimal_route(text: &str) -> AocResult<Canvas> {
+    let maze_map = Dec16.parse(text)?;
 
-fn shorten_wormholes(
-    maze_map: &HashMap<Position, Location>, 
-    wormholes: Vec<Wormhole>, 
-    positions: &[Position]
-) -> Vec<Wormhole> {
-    let mut wormholes_result = wormholes
+    let mut route = find_optimal_routes(&maze_map)
+        .path()
+        .ok_or(AocError::unsolvable("There is no path from the start tile to the end tile"))?
         .iter()
-        .filter(|w| positions
-            .iter()
-            .all(|p| w.one.position != *p && w.other.position != *p)
-        )
-        .cloned()
-        .collect::<Vec<Wormhole>>();
-
-    for p in positions {
-        let location = maze_map.get(p).unwrap();
-
-        if location.is_start_tile() || location.is_end_tile() {
-            wormholes_result.append(&mut wormholes.iter().filter(|&w| w.one.position == *p || w.other.position == *p).cloned().collect::<Vec<Wormhole>>());
-            continue;
-        }
-        
-        let (wormholes_starting_at_position, wormholes_ending_at_position): (Vec<Wormhole>, Vec<Wormhole>) = wormholes
-            .iter()
-            .filter(|&w| w.one.position == *p || w.other.position == *p)
-            .partition(|w| w.one.position == *p);
-
-        // for w in wormholes_ending_at_position.iter() {
-        //     println!("{:?}", w);
-        // }
-
-        // for w in wormholes_starting_at_position.iter() {
-        //     println!("{:?}", w);
-        // }
-
-        let we0 = wormholes_ending_at_position[0];
-        let ws0 = wormholes_starting_at_position.iter().find(|w| w.other.position != we0.one.position);
-
-        let we1 = wormholes_ending_at_position[1];
-        let ws1 = wormholes_starting_at_position.iter().find(|w| w.other.position != we1.one.position);
-        
-        if ws0.is_none() || ws1.is_none() {
-            // wormholes_result.append(&mut wormholes.iter().filter(|&w| w.one.position == *p || w.other.position == *p).cloned().collect::<Vec<Wormhole>>());
-            continue;
-        }
-
-        let ws0 = ws0.unwrap().clone();
-        let ws1 = ws1.unwrap().clone();
+        .map(|s| s.position)
+        .collect::<Vec<Position>>();
 
-        let w0 = Wormhole { one: we0.one, other: ws0.other, cost: we0.cost + ws0.cost };
-        let w1 = Wormhole { one: we1.one, other: ws1.other, cost: we1.cost + ws1.cost };
+    // Turning on the spot doesn't move the reindeer
+    route.dedup();
 
-        wormholes_result.push(w0);
-        wormholes_result.push(w1);
-    }
-
-    wormholes_result
-}
-
-pub fn run_second(is_real: bool) -> usize {
-    0
-}
-
-fn get_intersection(maze_map: &HashMap<Position, Location>, position: &Position) -> Option<Intersection> {
-    let location = maze_map.get(position).unwrap();
-    
-    let n_free = maze_map.get(&position.walk_in_direction(&Direction::N).unwrap()).unwrap().is_free();
-    let e_free = maze_map.get(&position.walk_in_direction(&Direction::E).unwrap()).unwrap().is_free();
-    let s_free = maze_map.get(&position.walk_in_direction(&Direction::S).unwrap()).unwrap().is_free();
-    let w_free = maze_map.get(&position.walk_in_direction(&Direction::W).unwrap()).unwrap().is_free();
-    
-    let total_free = vec![n_free, e_free, s_free, w_free].iter().filter(|x| **x).count();
-
-    if total_free > 2 || location.is_start_tile() || location.is_end_tile() {
-        return Some(Intersection { position: position.clone(), n_free, e_free, s_free, w_free });
-    }
+    let mut canvas = Canvas::from_grid(&maze_map, |l| match l {
+        Location::Obstruction => CellStyle::coloured('#', Colour::GREY),
+        Location::Free => CellStyle::plain('.'),
+        Location::StartTile => CellStyle::coloured('S', Colour::GREEN),
+        Location::EndTile => CellStyle::coloured('E', Colour::GREEN),
+    });
 
-    None
-}
+    canvas.draw_path(&route, Colour::RED);
 
-#[derive(Debug, Clone)]
-struct Intersection {
-    position: Position,
-    n_free: bool,
-    e_free: bool,
-    s_free: bool,
-    w_free: bool,
+    Ok(canvas)
 }
 
-impl Intersection {
-    fn get_possible_states(&self) -> Vec<ReindeerState> {
-        let mut possible_states = Vec::new();
+const FORWARD_COST: usize = 1;
+const TURN_COST: usize = 1000;
 
-        if self.n_free { possible_states.push(ReindeerState { position: self.position.clone(), direction: Direction::N }); }
-        if self.e_free { possible_states.push(ReindeerState { position: self.position.clone(), direction: Direction::E }); }
-        if self.s_free { possible_states.push(ReindeerState { position: self.position.clone(), direction: Direction::S }); }
-        if self.w_free { possible_states.push(ReindeerState { position: self.position.clone(), direction: Direction::W }); }
+fn find_optimal_routes(maze_map: &Grid<Location>) -> SearchResult<ReindeerState> {
+    let start_position = maze_map.find(|l| l.is_start_tile()).unwrap();
+    let initial_state = ReindeerState { position: start_position, direction: Direction::E };
 
-        possible_states
-    }
+    search::dijkstra(
+        [initial_state],
+        |state| state.next_states(maze_map),
+        |state| maze_map.get(&state.position).is_some_and(|l| l.is_end_tile()),
+    )
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 struct ReindeerState {
     position: Position,
     direction: Direction,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Wormhole {
-    one: ReindeerState,
-    other: ReindeerState,
-    cost: usize,
-}
-
-impl Wormhole {
-    fn from_state(
-        state: ReindeerState, 
-        maze_map: &HashMap<Position, Location>, 
-        intersections: &[Intersection]
-    ) -> Option<Self> {
-        let mut current_position = state.position.walk_in_direction(&state.direction).unwrap();
-        let mut current_direction = state.direction;
-        let mut current_cost = 1;
-
-        loop {
-            if let Some(intersection) = intersections.iter().find(|i| i.position == current_position) {
-                return Some(Self { 
-                    one: state, 
-                    other: ReindeerState { 
-                        position: intersection.position, 
-                        direction: current_direction 
-                    }, 
-                    cost: current_cost 
-                })
-            }
-
-            let forward_position = current_position.walk_in_direction(&current_direction).unwrap();
-            if maze_map.get(&forward_position).unwrap().is_free() {
-                current_position = forward_position;
-                current_cost = current_cost + 1;
-                continue;
-            }
-
-            let left_position = current_position.walk_in_direction(&current_direction.turn_left()).unwrap();
-            if maze_map.get(&left_position).unwrap().is_free() {
-                current_position = left_position;
-                current_direction = current_direction.turn_left();
-                current_cost = current_cost + 1001;
-                continue;
-            }
+impl ReindeerState {
+    fn next_states(&self, maze_map: &Grid<Location>) -> Vec<(ReindeerState, usize)> {
+        let mut next_states = vec![
+            (ReindeerState { position: self.position, direction: self.direction.turn_left() }, TURN_COST),
+            (ReindeerState { position: self.position, direction: self.direction.turn_right() }, TURN_COST),
+        ];
 
-            let right_position = current_position.walk_in_direction(&current_direction.turn_right()).unwrap();
-            if maze_map.get(&right_position).unwrap().is_free() {
-                current_position = right_position;
-                current_direction = current_direction.turn_right();
-                current_cost = current_cost + 1001;
-                continue;
+        if let Some(forward_position) = self.position.walk_in_direction(&self.direction) {
+            if maze_map.get(&forward_position).is_some_and(|l| l.is_free()) {
+                next_states.push((ReindeerState { position: forward_position, direction: self.direction }, FORWARD_COST));
             }
-
-            return None;
         }
-    }
 
-    fn travel_to_other_side(&self, state: &ReindeerState) -> Option<(ReindeerState, usize)> {
-        if state == &self.one {
-            Some((self.other.clone(), self.cost))
-        } else {
-            None
-        }
+        next_states
     }
 }
 
-enum Location {
+pub enum Location {
     Obstruction,
     Free,
     StartTile,
@@ -276,131 +112,26 @@ enum Location {
 }
 
 impl Location {
-    fn from_char(c: char) -> Location {
+    fn from_char(c: char) -> Option<Location> {
         match c {
-            '#' => Location::Obstruction,
-            '.' => Location::Free,
-            'S' => Location::StartTile,
-            'E' => Location::EndTile,
-            _ => panic!("Unexpected character")
+            '#' => Some(Location::Obstruction),
+            '.' => Some(Location::Free),
+            'S' => Some(Location::StartTile),
+            'E' => Some(Location::EndTile),
+            _ => None,
         }
     }
 
     fn is_start_tile(&self) -> bool {
-        match self {
-            Location::StartTile => true,
-            _ => false,
-        }
+        matches!(self, Location::StartTile)
     }
 
     fn is_end_tile(&self) -> bool {
-        match self {
-            Location::EndTile => true,
-            _ => false,
-        }
+        matches!(self, Location::EndTile)
     }
 
     fn is_free(&self) -> bool {
-        match self {
-            Location::Free | Location::StartTile | Location::EndTile => true,
-            _ => false,
-        }
-    }
-}
-
-// todo: start by finding paths from start tile to intersections
-struct IntersectionNode<'a> {
-    state: ReindeerState,
-    cost_from_start: usize,
-    parent_node: Option<&'a IntersectionNode<'a>>
-}
-
-impl<'a> IntersectionNode<'a> {
-    fn new(position: &Position) -> Self {
-        Self { 
-            state: ReindeerState { 
-                position: position.clone(), 
-                direction: Direction::E 
-            }, 
-            cost_from_start: 0, 
-            parent_node: None 
-        }
-    }
-
-    fn new_from_direction(parent_node: &'a IntersectionNode, bearing: Bearing, wormholes: &[Wormhole]) -> Option<Self> {
-        let parent_state = parent_node.state;
-        let state = match bearing {
-            Bearing::Forward => parent_state,
-            Bearing::Left => ReindeerState { position: parent_state.position, direction: parent_state.direction.turn_left() },
-            Bearing::Right => ReindeerState { position: parent_state.position, direction: parent_state.direction.turn_right() },
-        };
-
-        if let Some((new_state, wormhole_cost)) = wormholes.iter().filter_map(|wh| wh.travel_to_other_side(&state)).next() {
-            if ancestor_has_been_here(parent_node, &new_state.position) {
-                return None;
-            }
-
-            return Some(
-                IntersectionNode { 
-                    state: new_state, 
-                    cost_from_start: parent_node.cost_from_start + wormhole_cost + bearing.cost(), 
-                    parent_node: Some(parent_node) 
-                }
-            )
-        };
-        
-        None
-    }
-
-    fn get_optimal_cost(
-        &self, 
-        maze_map: &HashMap<Position, Location>, 
-        intersections: &[Intersection], 
-        wormholes: &[Wormhole]
-    ) -> Option<usize> {
-        let location = maze_map.get(&self.state.position).unwrap();
-        
-        if location.is_end_tile() {
-            return Some(self.cost_from_start);
-        }
-
-        bearings()
-            .into_iter()
-            .filter_map(|b| IntersectionNode::new_from_direction(self, b, wormholes))
-            .filter_map(|n| n.get_optimal_cost(maze_map, intersections, wormholes))
-            .min()
-    }
-}
-
-enum Bearing {
-    Forward,
-    Left,
-    Right
-}
-
-impl Bearing {
-    fn cost(&self) -> usize {
-        match self {
-            Bearing::Forward => 0,
-            Bearing::Left | Bearing::Right => 1000,
-        }
-    }
-}
-
-fn bearings() -> Vec<Bearing> {
-    vec![Bearing::Forward, Bearing::Left, Bearing::Right]
-}
-
-fn ancestor_has_been_here(mut parent_node: &IntersectionNode, position: &Position) -> bool {
-    loop {
-        if &parent_node.state.position == position {
-            return true;
-        }
-
-        parent_node = match parent_node.parent_node {
-            Some(pn) => pn,
-            None => return false,
-        };
+        matches!(self, Location::Free | Location::StartTile | Location::EndTile)
     }
 }
 
@@ -410,21 +141,33 @@ mod tests {
 
     #[test]
     fn test_run_first() {
-        assert_eq!(run_first(false), 7036);
+        assert_eq!(run_first(false).unwrap(), 7036);
+    }
+
+    #[test]
+    fn test_render_optimal_route() {
+        let text = crate::util::read_single_string_from_file(false, 16, None).unwrap();
+        let canvas = render_optimal_route(&text).unwrap();
+        let ascii = canvas.to_ascii();
+
+        // 36 steps forward and 7 turns make up the cost of 7036
+        assert_eq!(ascii.chars().filter(|c| "^>v<".contains(*c)).count(), 36);
+        assert_eq!(canvas.get(&Position { x: 1, y: 13 }).map(|c| c.symbol), Some('^'));
+        assert_eq!(canvas.get(&Position { x: 13, y: 1 }).map(|c| c.symbol), Some('E'));
     }
 
     // #[test]
     // fn real_run_first() {
-    //     assert_eq!(run_first(true), xx);
+    //     assert_eq!(run_first(true).unwrap(), xx);
     // }
 
     // #[test]
     // fn test_run_second() {
-    //     assert_eq!(run_second(false), xx);
+    //     assert_eq!(run_second(false).unwrap(), xx);
     // }
 
     // #[test]
     // fn real_run_second() {
-    //     assert_eq!(run_second(true), xx);
+    //     assert_eq!(run_second(true).unwrap(), xx);
     // }
 }
@@ -1,32 +1,56 @@
-pub fn run_first(is_real: bool) -> usize {
-    0
+use crate::error::AocResult;
+use crate::solution::{Answer, Day, Part, Solution};
+use crate::util::split_lines;
+
+pub struct DecXx;
+
+impl Solution for DecXx {
+    type Input = Vec<String>;
+
+    const DAY: u32 = 0;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input> {
+        Ok(split_lines(text))
+    }
+
+    fn part_one(&self, _lines: &Self::Input) -> AocResult<Answer> {
+        Ok(0)
+    }
+
+    fn part_two(&self, _lines: &Self::Input) -> AocResult<Answer> {
+        Ok(0)
+    }
+}
+
+pub fn run_first(is_real: bool) -> AocResult<Answer> {
+    DecXx.run(Part::One, is_real)
 }
 
-pub fn run_second(is_real: bool) -> usize {
-    0
+pub fn run_second(is_real: bool) -> AocResult<Answer> {
+    DecXx.run(Part::Two, is_real)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    // use super::*;
 
     // #[test]
     // fn test_run_first() {
-    //     assert_eq!(run_first(false), xx);
+    //     assert_eq!(run_first(false).unwrap(), xx);
     // }
 
     // #[test]
     // fn real_run_first() {
-    //     assert_eq!(run_first(true), xx);
+    //     assert_eq!(run_first(true).unwrap(), xx);
     // }
 
     // #[test]
     // fn test_run_second() {
-    //     assert_eq!(run_second(false), xx);
+    //     assert_eq!(run_second(false).unwrap(), xx);
     // }
 
     // #[test]
     // fn real_run_second() {
-    //     assert_eq!(run_second(true), xx);
+    //     assert_eq!(run_second(true).unwrap(), xx);
     // }
 }
@@ -0,0 +1,207 @@
+use std::fmt;
+
+use crate::input::InputError;
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(InputError),
+    Parse(ParseError),
+    Unsolvable(String),
+    Scaffold(String),
+    Http(String),
+}
+
+pub type AocResult<T> = Result<T, AocError>;
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub line_number: Option<usize>, // 1-based
+    pub column: Option<usize>, // 1-based
+    pub line: Option<String>,
+    pub section: Option<usize>, // 1-based
+}
+
+impl AocError {
+    pub fn parse(message: impl Into<String>) -> Self {
+        AocError::Parse(ParseError { message: message.into(), line_number: None, column: None, line: None, section: None })
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        AocError::Unsolvable(message.into())
+    }
+
+    pub fn scaffold(message: impl Into<String>) -> Self {
+        AocError::Scaffold(message.into())
+    }
+
+    pub fn http(message: impl Into<String>) -> Self {
+        AocError::Http(message.into())
+    }
+
+    // Line and column indices are 0-based, like the ones coming out of enumerate()
+    pub fn at_line(self, line_index: usize, line: &str) -> Self {
+        match self {
+            AocError::Parse(parse_error) if parse_error.line_number.is_none() => AocError::Parse(ParseError {
+                line_number: Some(line_index + 1),
+                line: Some(line.to_string()),
+                ..parse_error
+            }),
+            _ => self,
+        }
+    }
+
+    pub fn at_column(self, column_index: usize) -> Self {
+        match self {
+            AocError::Parse(parse_error) if parse_error.column.is_none() => AocError::Parse(ParseError {
+                column: Some(column_index + 1),
+                ..parse_error
+            }),
+            _ => self,
+        }
+    }
+
+    // Sections are the blank line separated blocks of an input, the index is 0-based
+    pub fn in_section(self, section_index: usize) -> Self {
+        match self {
+            AocError::Parse(parse_error) if parse_error.section.is_none() => AocError::Parse(ParseError {
+                section: Some(section_index + 1),
+                ..parse_error
+            }),
+            _ => self,
+        }
+    }
+
+    // Moves a line number that is relative to part of the input to the whole input
+    pub fn with_line_offset(self, line_offset: usize) -> Self {
+        match self {
+            AocError::Parse(parse_error) => AocError::Parse(ParseError {
+                line_number: parse_error.line_number.map(|n| n + line_offset),
+                ..parse_error
+            }),
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(error) => write!(f, "{}", error),
+            AocError::Parse(parse_error) => write!(f, "{}", parse_error),
+            AocError::Unsolvable(message) => write!(f, "Input cannot be solved: {}", message),
+            AocError::Scaffold(message) => write!(f, "Cannot create day: {}", message),
+            AocError::Http(message) => write!(f, "Request failed: {}", message),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Parse error")?;
+
+        if let Some(section) = self.section {
+            write!(f, " in section {}", section)?;
+        }
+
+        match (self.line_number, self.column) {
+            (Some(line_number), Some(column)) => write!(f, " on line {}, column {}", line_number, column)?,
+            (Some(line_number), None) => write!(f, " on line {}", line_number)?,
+            (None, Some(column)) => write!(f, " in column {}", column)?,
+            (None, None) => (),
+        }
+
+        write!(f, ": {}", self.message)?;
+
+        if let Some(line) = &self.line {
+            write!(f, "\n    {}", line)?;
+
+            if let Some(column) = self.column {
+                write!(f, "\n    {}^", " ".repeat(column - 1))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<InputError> for AocError {
+    fn from(error: InputError) -> Self {
+        AocError::Io(error)
+    }
+}
+
+pub fn parse_lines<T>(
+    text: &str,
+    parse_line: impl Fn(&str) -> AocResult<T>,
+) -> AocResult<Vec<T>> {
+    text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(line).map_err(|e| e.at_line(index, line)))
+        .collect()
+}
+
+pub fn parse_non_empty_lines<T>(
+    text: &str,
+    parse_line: impl Fn(&str) -> AocResult<T>,
+) -> AocResult<Vec<T>> {
+    text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse_line(line).map_err(|e| e.at_line(index, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_number(line: &str) -> AocResult<i32> {
+        line.parse::<i32>().map_err(|e| AocError::parse(e.to_string()))
+    }
+
+    #[test]
+    fn test_parse_lines() {
+        assert_eq!(parse_lines("1\n2\n3", parse_number).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_lines_reports_offending_line() {
+        let Err(AocError::Parse(parse_error)) = parse_lines("1\n2\nthree", parse_number) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!(parse_error.line_number, Some(3));
+        assert_eq!(parse_error.line, Some("three".to_string()));
+    }
+
+    #[test]
+    fn test_parse_non_empty_lines_skips_blank_lines() {
+        assert_eq!(parse_non_empty_lines("1\n\n3\n", parse_number).unwrap(), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_display_points_at_column() {
+        let error = AocError::parse("Unexpected character 'x'").at_column(2).at_line(4, "..x.");
+
+        assert_eq!(error.to_string(), "Parse error on line 5, column 3: Unexpected character 'x'\n    ..x.\n      ^");
+    }
+
+    #[test]
+    fn test_display_names_section() {
+        let error = AocError::parse("Bad").at_line(1, "x").with_line_offset(4).in_section(1);
+
+        assert_eq!(error.to_string(), "Parse error in section 2 on line 6: Bad\n    x");
+    }
+
+    #[test]
+    fn test_location_is_not_overwritten() {
+        let error = AocError::parse("Bad").at_line(0, "inner").at_line(7, "outer");
+
+        assert_eq!(error.to_string(), "Parse error on line 1: Bad\n    inner");
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::solution::{Day, Part};
+
+pub const INPUT_DIRECTORY_VARIABLE: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Data { is_real: bool, day: u32, order: Option<u32> },
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound { file_name: String, tried: Vec<PathBuf> },
+    Unreadable { path: PathBuf, error: std::io::Error },
+    Unwritable { path: PathBuf, error: std::io::Error },
+    Stdin(std::io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { file_name, tried } => {
+                write!(f, "Could not find {}, tried:", file_name)?;
+
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+
+                Ok(())
+            },
+            InputError::Unreadable { path, error } => write!(f, "Failed to read {}: {}", path.display(), error),
+            InputError::Unwritable { path, error } => write!(f, "Failed to write {}: {}", path.display(), error),
+            InputError::Stdin(error) => write!(f, "Failed to read from stdin: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn read_input(source: &InputSource) -> Result<String, InputError> {
+    match source {
+        InputSource::Data { is_real, day, order } => {
+            let path = resolve_data_file(&data_directories(), *is_real, *day, *order)?;
+
+            read_path(&path)
+        },
+        InputSource::File(path) => read_path(path),
+        InputSource::Stdin => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text).map_err(InputError::Stdin)?;
+
+            Ok(text)
+        },
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSelection {
+    Test,
+    Real,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSelection {
+    // The order only applies to data files, without one a day picks the file for the part itself
+    pub fn source_for(&self, day: &dyn Day, part: Part, order: Option<u32>) -> InputSource {
+        match self {
+            InputSelection::File(path) => InputSource::File(path.clone()),
+            InputSelection::Stdin => InputSource::Stdin,
+            InputSelection::Test | InputSelection::Real => {
+                let is_real = *self == InputSelection::Real;
+                let order = order.or(day.input_order(is_real, part));
+
+                InputSource::Data { is_real, day: day.day(), order }
+            },
+        }
+    }
+}
+
+// Every input is read a single time, stdin can only be consumed once and is shared between the parts
+#[derive(Default)]
+pub struct InputCache {
+    texts: Vec<(InputSource, String)>,
+}
+
+impl InputCache {
+    pub fn new() -> Self {
+        InputCache::default()
+    }
+
+    pub fn read(&mut self, source: &InputSource) -> Result<&str, InputError> {
+        let index = match self.texts.iter().position(|(s, _)| s == source) {
+            Some(index) => index,
+            None => {
+                let text = read_input(source)?;
+                self.texts.push((source.clone(), text));
+
+                self.texts.len() - 1
+            },
+        };
+
+        Ok(&self.texts[index].1)
+    }
+
+    pub fn get(&self, source: &InputSource) -> Option<&str> {
+        self.texts.iter().find(|(s, _)| s == source).map(|(_, text)| text.as_str())
+    }
+}
+
+// Directories holding the dec_NN folders, in the order they are searched
+pub fn data_directories() -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+
+    if let Some(directory) = std::env::var_os(INPUT_DIRECTORY_VARIABLE) {
+        directories.push(PathBuf::from(directory));
+    }
+
+    directories.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("src"));
+
+    directories
+}
+
+pub fn data_file_name(is_real: bool, order: Option<u32>) -> String {
+    let suffix = match order {
+        Some(o) => format!("_{}", o),
+        None => "".to_string()
+    };
+
+    match is_real {
+        true => format!("real_data{}.txt", suffix),
+        false => format!("test_data{}.txt", suffix),
+    }
+}
+
+pub fn day_folder_name(day: u32) -> String {
+    format!("dec_{:02}", day)
+}
+
+pub fn resolve_data_file(
+    directories: &[PathBuf],
+    is_real: bool,
+    day: u32,
+    order: Option<u32>,
+) -> Result<PathBuf, InputError> {
+    let file_name = data_file_name(is_real, order);
+    let tried = directories
+        .iter()
+        .map(|d| d.join(day_folder_name(day)).join(&file_name))
+        .collect::<Vec<PathBuf>>();
+
+    match tried.iter().find(|p| p.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::NotFound { file_name, tried }),
+    }
+}
+
+fn read_path(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|error| InputError::Unreadable { path: path.to_path_buf(), error })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn create_data_directory(name: &str, day: u32, file_name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("aoc-2024-{}-{}", name, std::process::id()));
+        let day_folder = directory.join(day_folder_name(day));
+
+        std::fs::create_dir_all(&day_folder).unwrap();
+        std::fs::write(day_folder.join(file_name), "1 2 3").unwrap();
+
+        directory
+    }
+
+    #[test_case(true, None, "real_data.txt")]
+    #[test_case(false, None, "test_data.txt")]
+    #[test_case(false, Some(2), "test_data_2.txt")]
+    fn test_data_file_name(is_real: bool, order: Option<u32>, expected_result: &str) {
+        assert_eq!(data_file_name(is_real, order), expected_result);
+    }
+
+    #[test]
+    fn test_resolve_data_file_prefers_first_directory() {
+        let first_directory = create_data_directory("first", 7, "test_data.txt");
+        let second_directory = create_data_directory("second", 7, "test_data.txt");
+
+        let result = resolve_data_file(&[first_directory.clone(), second_directory], false, 7, None).unwrap();
+
+        assert_eq!(result, first_directory.join("dec_07").join("test_data.txt"));
+    }
+
+    #[test]
+    fn test_resolve_data_file_falls_back() {
+        let empty_directory = std::env::temp_dir().join("aoc-2024-does-not-exist");
+        let data_directory = create_data_directory("fallback", 3, "real_data.txt");
+
+        let result = resolve_data_file(&[empty_directory, data_directory.clone()], true, 3, None).unwrap();
+
+        assert_eq!(result, data_directory.join("dec_03").join("real_data.txt"));
+    }
+
+    #[test]
+    fn test_resolve_data_file_lists_tried_paths() {
+        let directories = vec![PathBuf::from("/nowhere"), PathBuf::from("/elsewhere")];
+
+        let error = resolve_data_file(&directories, false, 12, Some(1)).unwrap_err();
+        let message = error.to_string();
+
+        assert!(message.contains("test_data_1.txt"));
+        assert!(message.contains(&PathBuf::from("/nowhere/dec_12/test_data_1.txt").display().to_string()));
+        assert!(message.contains(&PathBuf::from("/elsewhere/dec_12/test_data_1.txt").display().to_string()));
+    }
+
+    #[test]
+    fn test_input_selection_source() {
+        let day = crate::registry::find_day(1).unwrap();
+
+        assert_eq!(InputSelection::Test.source_for(day, Part::Two, None), InputSource::Data { is_real: false, day: 1, order: None });
+        assert_eq!(InputSelection::Real.source_for(day, Part::One, Some(2)), InputSource::Data { is_real: true, day: 1, order: Some(2) });
+        assert_eq!(InputSelection::Stdin.source_for(day, Part::One, None), InputSource::Stdin);
+    }
+
+    #[test]
+    fn test_input_cache_reads_once() {
+        let directory = create_data_directory("cache", 1, "input.txt");
+        let path = directory.join("dec_01").join("input.txt");
+        let source = InputSource::File(path.clone());
+        let mut inputs = InputCache::new();
+
+        assert_eq!(inputs.read(&source).unwrap(), "1 2 3");
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(inputs.read(&source).unwrap(), "1 2 3");
+        assert_eq!(inputs.get(&source), Some("1 2 3"));
+        assert!(inputs.read(&InputSource::File(directory.join("missing.txt"))).is_err());
+    }
+
+    #[test]
+    fn test_read_input_from_manifest_directory() {
+        let text = read_input(&InputSource::Data { is_real: false, day: 1, order: None }).unwrap();
+
+        assert!(!text.is_empty());
+    }
+}
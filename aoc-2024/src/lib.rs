@@ -0,0 +1,46 @@
+pub mod answers;
+pub mod benchmark;
+pub mod client;
+
+#[allow(dead_code)]
+pub mod dec_01;
+#[allow(dead_code)]
+pub mod dec_02;
+#[allow(dead_code)]
+pub mod dec_03;
+#[allow(dead_code)]
+pub mod dec_04;
+#[allow(dead_code)]
+pub mod dec_05;
+#[allow(dead_code)]
+pub mod dec_06;
+#[allow(dead_code)]
+pub mod dec_07;
+#[allow(dead_code)]
+pub mod dec_08;
+#[allow(dead_code)]
+pub mod dec_09;
+#[allow(dead_code)]
+pub mod dec_10;
+#[allow(dead_code)]
+pub mod dec_11;
+#[allow(dead_code)]
+pub mod dec_12;
+#[allow(dead_code)]
+pub mod dec_13;
+#[allow(dead_code)]
+pub mod dec_14;
+#[allow(dead_code)]
+pub mod dec_15;
+#[allow(dead_code)]
+pub mod dec_16;
+
+pub mod error;
+pub mod input;
+pub mod puzzle;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod util;
@@ -1,103 +1,279 @@
-#[allow(dead_code)]
-mod dec_01;
-#[allow(dead_code)]
-mod dec_02;
-#[allow(dead_code)]
-mod dec_03;
-#[allow(dead_code)]
-mod dec_04;
-#[allow(dead_code)]
-mod dec_05;
-#[allow(dead_code)]
-mod dec_06;
-#[allow(dead_code)]
-mod dec_07;
-#[allow(dead_code)]
-mod dec_08;
-#[allow(dead_code)]
-mod dec_09;
-#[allow(dead_code)]
-mod dec_10;
-#[allow(dead_code)]
-mod dec_11;
-#[allow(dead_code)]
-mod dec_12;
-#[allow(dead_code)]
-mod dec_13;
-#[allow(dead_code)]
-mod dec_14;
-#[allow(dead_code)]
-mod dec_15;
-mod dec_16;
-
-mod util;
+mod cli;
+
+use aoc_2024::answers::{self, answers_file_path, AnswerKey, AnswerStore};
+use aoc_2024::client::{self, AocClient, Submission, SubmissionOutcome};
+use aoc_2024::error::AocError;
+use aoc_2024::input::{read_input, InputCache, InputError, InputSource};
+use aoc_2024::report::{format_results, ordinal, OutputFormat};
+use aoc_2024::runner::{default_thread_count, run_in_parallel, run_in_sequence, select_parts, PartSelection};
+use aoc_2024::{benchmark, puzzle, registry, scaffold};
+use cli::{BenchOptions, Command, ImportOptions, RecordOptions, RenderOptions, RunOptions, SubmitOptions};
 
 fn main() {
-    // println!("December 1st");
-    // println!("Part 1: {}", dec_01::run_first(true));
-    // println!("Part 2: {}", dec_01::run_second(true));
+    let arguments = std::env::args().skip(1).collect::<Vec<String>>();
+
+    let command = match cli::parse_arguments(&arguments) {
+        Ok(Command::Help) => {
+            println!("{}", cli::USAGE);
+            return;
+        },
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, cli::USAGE);
+            std::process::exit(2);
+        },
+    };
+
+    let all_succeeded = match command {
+        Command::Run(options) => run(&options),
+        Command::Bench(options) => bench(&options),
+        Command::Record(options) => record(&options),
+        Command::NewDay(day) => new_day(day),
+        Command::Import(options) => import(&options),
+        Command::Fetch(day) => fetch(day),
+        Command::Submit(options) => submit(&options),
+        Command::Render(options) => render(&options),
+        Command::Help => true,
+    };
+
+    if !all_succeeded {
+        std::process::exit(1);
+    }
+}
 
-    // println!("December 2nd");
-    // println!("Part 1: {}", dec_02::run_first(true));
-    // println!("Part 2: {}", dec_02::run_second(true));
+fn run(options: &RunOptions) -> bool {
+    let selections = match registry::select_days(&options.days) {
+        Ok(days) => select_parts(&days, &options.parts, &options.input, options.order),
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        },
+    };
 
-    // println!("December 3rd");
-    // println!("Part 1: {}", dec_03::run_first(true));
-    // println!("Part 2: {}", dec_03::run_second(true));
+    let answer_store = match AnswerStore::load(&answers_file_path()) {
+        Ok(answer_store) => answer_store,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        },
+    };
 
-    // println!("December 4th");
-    // println!("Part 1: {}", dec_04::run_first(true));
-    // println!("Part 2: {}", dec_04::run_second(true));
+    let mut inputs = InputCache::new();
 
-    // println!("December 5th");
-    // println!("Part 1: {}", dec_05::run_first(true));
-    // println!("Part 2: {}", dec_05::run_second(true));
+    match (options.parallel, options.format) {
+        (false, OutputFormat::Text) => print_in_sequence(&selections, &mut inputs, &answer_store),
+        (true, _) => print_report(&selections, options.format, &mut inputs, &answer_store, default_thread_count()),
+        (false, _) => print_report(&selections, options.format, &mut inputs, &answer_store, 1),
+    }
+}
+
+fn print_in_sequence(selections: &[PartSelection], inputs: &mut InputCache, answer_store: &AnswerStore) -> bool {
+    let mut current_day = None;
+    let mut any_part_failed = false;
+
+    run_in_sequence(selections, inputs, answer_store, |result| {
+        if current_day != Some(result.day) {
+            println!("December {}", ordinal(result.day));
+            current_day = Some(result.day);
+        }
 
-    // println!("December 6th");
-    // println!("Part 1: {}", dec_06::run_first(true));
-    // println!("Part 2: {}", dec_06::run_second(true));
+        match (&result.answer, result.status()) {
+            (Ok(answer), Some(status)) => println!("Part {}: {} {}", result.part.number(), answer, status),
+            (Err(error), _) => eprintln!("Part {}: {}", result.part.number(), error),
+            _ => (),
+        }
 
-    // println!("December 7th");
-    // println!("Part 1: {}", dec_07::run_first(true));
-    // println!("Part 2: {}", dec_07::run_second(true));
+        any_part_failed |= result.is_failure();
+    });
+
+    !any_part_failed
+}
 
-    // println!("December 8th");
-    // println!("Part 1: {}", dec_08::run_first(true));
-    // println!("Part 2: {}", dec_08::run_second(true));
+// Every result is collected before they are printed together, as a table or in a machine readable format
+fn print_report(
+    selections: &[PartSelection],
+    format: OutputFormat,
+    inputs: &mut InputCache,
+    answer_store: &AnswerStore,
+    thread_count: usize,
+) -> bool {
+    let results = run_in_parallel(selections, inputs, answer_store, thread_count);
 
-    // println!("December 9th");
-    // println!("Part 1: {}", dec_09::run_first(true));
-    // println!("Part 2: {}", dec_09::run_second(true));
+    println!("{}", format_results(&results, format));
 
-    // println!("December 10th");
-    // println!("Part 1: {}", dec_10::run_first(true));
-    // println!("Part 2: {}", dec_10::run_second(true));
+    for result in results.iter() {
+        if let Err(error) = &result.answer {
+            eprintln!("December {}, part {}: {}", ordinal(result.day), result.part.number(), error);
+        }
+    }
+
+    !results.iter().any(|r| r.is_failure())
+}
+
+fn record(options: &RecordOptions) -> bool {
+    let key = AnswerKey { day: options.day, part: options.part, is_real: options.is_real };
+
+    match answers::record_answer(&answers_file_path(), key, options.answer) {
+        Ok((answer, Some(previous_answer))) if previous_answer != answer => println!("Replaced {} with {}", previous_answer, answer),
+        Ok((answer, Some(_))) => println!("{} was already recorded", answer),
+        Ok((answer, None)) => println!("Recorded {}", answer),
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        },
+    }
+
+    true
+}
+
+fn new_day(day: u32) -> bool {
+    match scaffold::create_day(&scaffold::crate_directory(), day) {
+        Ok(changed_files) => {
+            for path in changed_files {
+                println!("Wrote {}", path.display());
+            }
+
+            true
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        },
+    }
+}
+
+fn import(options: &ImportOptions) -> bool {
+    let result = std::fs::read_to_string(&options.page)
+        .map_err(|error| AocError::from(InputError::Unreadable { path: options.page.clone(), error }))
+        .and_then(|html| puzzle::import_examples(&scaffold::crate_directory(), options.day, &html));
+
+    match result {
+        Ok(changed_files) if changed_files.is_empty() => {
+            println!("The examples of December {} were already imported", ordinal(options.day));
+            true
+        },
+        Ok(changed_files) => {
+            for path in changed_files {
+                match path.exists() {
+                    true => println!("Wrote {}", path.display()),
+                    false => println!("Removed {}", path.display()),
+                }
+            }
+
+            true
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        },
+    }
+}
+
+fn fetch(day: u32) -> bool {
+    let result = AocClient::from_environment().and_then(|mut client| {
+        client.fetch_input(day)?;
+
+        Ok(client.cached_input_path(day))
+    });
+
+    match result {
+        Ok(path) => {
+            println!("The input of December {} is in {}", ordinal(day), path.display());
+            true
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        },
+    }
+}
+
+fn submit(options: &SubmitOptions) -> bool {
+    let submission = client::submit_and_record(
+        &answers_file_path(),
+        options.day,
+        options.part,
+        options.answer,
+        AocClient::from_environment,
+    );
+
+    match submission {
+        Ok(Submission::AlreadyRecorded(answer)) => {
+            println!("{} is already recorded as the answer, not submitting", answer);
+            true
+        },
+        Ok(Submission::Submitted { answer, outcome }) => {
+            println!("Submitted {}\n{}", answer, outcome);
+            outcome == SubmissionOutcome::Correct
+        },
+        Err(error) => {
+            eprintln!("{}", error);
+            false
+        },
+    }
+}
+
+fn render(options: &RenderOptions) -> bool {
+    let source = InputSource::Data { is_real: options.is_real, day: options.day, order: None };
+
+    let result = read_input(&source)
+        .map_err(AocError::from)
+        .and_then(|text| registry::render_day(options.day, &text))
+        .and_then(|canvas| match &options.output {
+            Some((path, format)) => {
+                canvas.save_image(path, *format, options.scale)?;
+                println!("Saved {}", path.display());
+
+                Ok(())
+            },
+            None => {
+                println!("{}", canvas.to_ansi());
+
+                Ok(())
+            },
+        });
+
+    if let Err(error) = &result {
+        eprintln!("{}", error);
+    }
+
+    result.is_ok()
+}
 
-    // println!("December 11th");
-    // println!("Part 1: {}", dec_11::run_first(true));
-    // println!("Part 2: {}", dec_11::run_second(true));
+fn bench(options: &BenchOptions) -> bool {
+    let run_options = &options.run_options;
 
-    // println!("December 12th");
-    // println!("Part 1: {}", dec_12::run_first(true));
-    // println!("Part 2: {}", dec_12::run_second(true));
+    let days = match registry::select_days(&run_options.days) {
+        Ok(days) => days,
+        Err(error) => {
+            eprintln!("{}", error);
+            return false;
+        },
+    };
 
-    // println!("December 13th");
-    // println!("Part 1: {}", dec_13::run_first(true));
-    // println!("Part 2: {}", dec_13::run_second(true));
+    let mut benchmarks = Vec::new();
+    let mut any_day_failed = false;
 
-    // println!("December 14th");
-    // println!("Part 1: {}", dec_14::run_first(true));
-    // println!("Part 2: {}", dec_14::run_second(true));
+    let day_benchmarks = benchmark::benchmark_days(
+        &days,
+        &run_options.parts,
+        options.runs,
+        &run_options.input,
+        run_options.order,
+        &mut InputCache::new(),
+    );
 
-    // println!("December 15th");
-    // println!("Part 1: {}", dec_15::run_first(true));
-    // println!("Part 2: {}", dec_15::run_second(true));
+    for (day, benchmark) in day_benchmarks {
+        match benchmark {
+            Ok(benchmark) => benchmarks.push(benchmark),
+            Err(error) => {
+                eprintln!("December {}: {}", ordinal(day), error);
+                any_day_failed = true;
+            },
+        }
+    }
 
-    println!("December 16th");
-    println!("Part 1: {}", dec_16::run_first(true));
-    // println!("Part 2: {}", dec_16::run_second(true));
+    println!("{}", benchmark::format_table(&benchmarks));
 
-    // println!("December xxth");
-    // println!("Part 1: {}", dec_xx::run_first(true));
-    // println!("Part 2: {}", dec_xx::run_second(true));
+    !any_day_failed
 }
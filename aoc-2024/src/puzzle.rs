@@ -0,0 +1,348 @@
+use std::path::{Path, PathBuf};
+
+use crate::answers::{AnswerKey, AnswerStore, ANSWERS_FILE_NAME};
+use crate::error::{AocError, AocResult};
+use crate::input::{data_file_name, day_folder_name, InputError};
+use crate::solution::{parts, Answer, Part};
+use crate::util::parsing::LazyRegex;
+
+// What a saved puzzle page tells about one part
+#[derive(Debug, PartialEq)]
+pub struct PuzzlePart {
+    pub part: Part,
+    pub examples: Vec<String>,
+    pub example_answer: Option<Answer>,
+}
+
+// Example inputs to write, keyed by data file order, together with the answers they should give
+#[derive(Debug, PartialEq)]
+pub struct ExampleImport {
+    pub inputs: Vec<(Option<u32>, String)>,
+    pub answers: Vec<(Part, Answer)>,
+}
+
+impl ExampleImport {
+    // The first example of a part is taken as its input. Part two only gets its own
+    // example files when its description starts with a different example.
+    pub fn from_puzzle_parts(puzzle_parts: &[PuzzlePart]) -> AocResult<Self> {
+        let first_examples = puzzle_parts.iter().map(|p| p.examples.first()).collect::<Vec<Option<&String>>>();
+
+        let inputs = match first_examples.as_slice() {
+            [Some(first), Some(second)] if first != second => {
+                vec![(Some(1), first.to_string()), (Some(2), second.to_string())]
+            },
+            [Some(first), ..] => vec![(None, first.to_string())],
+            _ => return Err(AocError::parse("The description of part one has no example")),
+        };
+
+        let answers = puzzle_parts
+            .iter()
+            .filter_map(|p| p.example_answer.map(|a| (p.part, a)))
+            .collect();
+
+        Ok(ExampleImport { inputs, answers })
+    }
+
+    pub fn has_separate_examples(&self) -> bool {
+        self.inputs.iter().any(|(order, _)| order.is_some())
+    }
+}
+
+// Every part is an <article class="day-desc">, the second one only shows up once part one is solved
+pub fn parse_puzzle_page(html: &str) -> AocResult<Vec<PuzzlePart>> {
+    static ARTICLE: LazyRegex = LazyRegex::new(r#"(?s)<article[^>]*class="day-desc"[^>]*>(.*?)</article>"#);
+    static EXAMPLE: LazyRegex = LazyRegex::new(r"(?s)<pre><code>(.*?)</code></pre>");
+    static HIGHLIGHTED: LazyRegex = LazyRegex::new(r"(?s)<code><em>(.*?)</em></code>");
+
+    let articles = ARTICLE.captures_iter(html).map(|c| c.get(1).unwrap().as_str()).collect::<Vec<&str>>();
+
+    if articles.is_empty() || articles.len() > 2 {
+        return Err(AocError::parse(format!("Expected one or two puzzle descriptions, found {}", articles.len())));
+    }
+
+    Ok(articles
+        .iter()
+        .zip(parts())
+        .map(|(article, part)| PuzzlePart {
+            part,
+            examples: EXAMPLE.captures_iter(article).map(|c| decode_html(&c[1])).collect(),
+            // The answer for the example is the last highlighted number of the description
+            example_answer: HIGHLIGHTED
+                .captures_iter(article)
+                .filter_map(|c| decode_html(&c[1]).trim().parse::<Answer>().ok())
+                .last(),
+        })
+        .collect())
+}
+
+// Writes the examples into the data folder of the day, turns on the example tests of src/dec_NN.rs
+// and records the example answers. Once every part has its own example, the shared test_data.txt
+// is removed so it can't go stale. Returns the files that were created, changed or removed.
+pub fn import_examples(crate_directory: &Path, day: u32, html: &str) -> AocResult<Vec<PathBuf>> {
+    let example_import = ExampleImport::from_puzzle_parts(&parse_puzzle_page(html)?)?;
+
+    let source_directory = crate_directory.join("src");
+    let module_path = source_directory.join(format!("{}.rs", day_folder_name(day)));
+    let data_directory = source_directory.join(day_folder_name(day));
+
+    if !module_path.is_file() || !data_directory.is_dir() {
+        return Err(AocError::scaffold(format!("{} does not exist yet, run new-day {} first", module_path.display(), day)));
+    }
+
+    // Everything is prepared before writing, so a conflict leaves the tree untouched
+    let mut changed_files = Vec::new();
+
+    for (order, example) in example_import.inputs.iter() {
+        let path = data_directory.join(data_file_name(false, *order));
+        let existing_example = std::fs::read_to_string(&path).unwrap_or_default();
+
+        if !existing_example.trim().is_empty() && existing_example.trim_end() != example.trim_end() {
+            return Err(AocError::scaffold(format!("{} already holds a different example", path.display())));
+        }
+
+        if existing_example != *example {
+            changed_files.push((path, example.to_string()));
+        }
+    }
+
+    let shared_example_path = data_directory.join(data_file_name(false, None));
+    let removes_shared_example = example_import.has_separate_examples() && shared_example_path.is_file();
+
+    let module = read_file(&module_path)?;
+    let mut updated_module = enable_example_tests(&module, &example_import.answers);
+
+    if example_import.has_separate_examples() {
+        updated_module = add_test_input_order(&updated_module)?;
+    }
+
+    if updated_module != module {
+        changed_files.push((module_path, updated_module));
+    }
+
+    let answers_path = crate_directory.join(ANSWERS_FILE_NAME);
+    let mut answer_store = AnswerStore::load(&answers_path)?;
+    let mut answers_changed = false;
+
+    for (part, answer) in example_import.answers.iter() {
+        answers_changed |= answer_store.record(AnswerKey { day, part: *part, is_real: false }, *answer) != Some(*answer);
+    }
+
+    if answers_changed {
+        changed_files.push((answers_path, answer_store.to_toml()));
+    }
+
+    for (path, text) in changed_files.iter() {
+        std::fs::write(path, text).map_err(|error| InputError::Unwritable { path: path.clone(), error })?;
+    }
+
+    let mut changed_paths = changed_files.into_iter().map(|(path, _)| path).collect::<Vec<PathBuf>>();
+
+    if removes_shared_example {
+        std::fs::remove_file(&shared_example_path)
+            .map_err(|error| InputError::Unwritable { path: shared_example_path.clone(), error })?;
+
+        changed_paths.push(shared_example_path);
+    }
+
+    Ok(changed_paths)
+}
+
+// Uncomments the example tests that the day template comes with, filling in the answers
+pub fn enable_example_tests(module: &str, answers: &[(Part, Answer)]) -> String {
+    let mut text = module.to_string();
+
+    for (part, answer) in answers {
+        let name = match part {
+            Part::One => "first",
+            Part::Two => "second",
+        };
+
+        let commented_test = format!(
+            "    // #[test]\n    // fn test_run_{0}() {{\n    //     assert_eq!(run_{0}(false).unwrap(), xx);\n    // }}",
+            name,
+        );
+        let test = format!(
+            "    #[test]\n    fn test_run_{0}() {{\n        assert_eq!(run_{0}(false).unwrap(), {1});\n    }}",
+            name,
+            answer,
+        );
+
+        text = text.replace(&commented_test, &test);
+    }
+
+    match text == module {
+        true => text,
+        false => text.replacen("    // use super::*;", "    use super::*;", 1),
+    }
+}
+
+// Points each part at its own example file, like dec_03 does
+pub fn add_test_input_order(module: &str) -> AocResult<String> {
+    if module.contains("fn test_input_order") {
+        return Ok(module.to_string());
+    }
+
+    let lines = module.lines().collect::<Vec<&str>>();
+
+    let impl_end = lines
+        .iter()
+        .position(|l| l.starts_with("impl Solution for"))
+        .and_then(|start| (start..lines.len()).find(|index| lines[*index] == "}"))
+        .ok_or(AocError::scaffold("Could not find the Solution implementation of the day"))?;
+
+    let method = ["", "    fn test_input_order(part: Part) -> Option<u32> {", "        Some(part.number())", "    }"];
+
+    let mut text = lines[..impl_end]
+        .iter()
+        .chain(method.iter())
+        .chain(lines[impl_end..].iter())
+        .copied()
+        .collect::<Vec<&str>>()
+        .join("\n");
+
+    if module.ends_with('\n') {
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+// Text of an HTML fragment, with the tags left out
+pub fn decode_html(html: &str) -> String {
+    static TAG: LazyRegex = LazyRegex::new(r"<[^>]*>");
+
+    TAG
+        .replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn read_file(path: &Path) -> AocResult<String> {
+    std::fs::read_to_string(path).map_err(|error| InputError::Unreadable { path: path.to_path_buf(), error }.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scaffold::{crate_directory, render_day_module};
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Mull It Over ---</h2>
+<p>For example, consider the following section of corrupted memory:</p>
+<pre><code>x<em>mul(2,4)</em>%&amp;mul[3,7]!@^do_not_<em>mul(5,5)</em>
+</code></pre>
+<p>Only the four highlighted sections are real <code>mul</code> instructions. Adding up the result of each instruction produces <code><em>161</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>191183308</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>x<em>mul(2,4)</em>&amp;mul[3,7]!^<em>don't()</em>_mul(5,5)
+</code></pre>
+<p>This time, the sum of the results is <code><em>48</em></code>.</p>
+</article>
+</main></body></html>"#;
+
+    fn first_part_only(page: &str) -> String {
+        let second_article = page.rfind("<article").unwrap();
+
+        format!("{}</main></body></html>", &page[..second_article])
+    }
+
+    #[test]
+    fn test_parse_puzzle_page() {
+        let puzzle_parts = parse_puzzle_page(PAGE).unwrap();
+
+        assert_eq!(
+            puzzle_parts,
+            vec![
+                PuzzlePart { part: Part::One, examples: vec!["xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)\n".to_string()], example_answer: Some(161) },
+                PuzzlePart { part: Part::Two, examples: vec!["xmul(2,4)&mul[3,7]!^don't()_mul(5,5)\n".to_string()], example_answer: Some(48) },
+            ],
+        );
+    }
+
+    #[test]
+    fn test_page_without_description() {
+        assert!(parse_puzzle_page("<html><body>Not found</body></html>").is_err());
+    }
+
+    #[test]
+    fn test_shared_example() {
+        let puzzle_parts = vec![
+            PuzzlePart { part: Part::One, examples: vec!["1 2".to_string(), "3".to_string()], example_answer: Some(11) },
+            PuzzlePart { part: Part::Two, examples: vec![], example_answer: Some(31) },
+        ];
+
+        let example_import = ExampleImport::from_puzzle_parts(&puzzle_parts).unwrap();
+
+        assert_eq!(example_import.inputs, vec![(None, "1 2".to_string())]);
+        assert_eq!(example_import.answers, vec![(Part::One, 11), (Part::Two, 31)]);
+        assert!(!example_import.has_separate_examples());
+    }
+
+    #[test]
+    fn test_enable_example_tests() {
+        let template = std::fs::read_to_string(crate_directory().join("src").join("dec_xx.rs")).unwrap();
+        let module = render_day_module(&template, 3).unwrap();
+
+        let updated_module = enable_example_tests(&module, &[(Part::One, 161)]);
+
+        assert!(updated_module.contains("    use super::*;"));
+        assert!(updated_module.contains("    #[test]\n    fn test_run_first() {\n        assert_eq!(run_first(false).unwrap(), 161);\n    }"));
+        assert!(updated_module.contains("    // fn test_run_second() {"));
+        assert_eq!(enable_example_tests(&module, &[]), module);
+    }
+
+    #[test]
+    fn test_add_test_input_order() {
+        let module = "impl Solution for Dec03 {\n    const DAY: u32 = 3;\n}\n\nfn other() {\n}\n";
+
+        let expected_module = "impl Solution for Dec03 {\n    const DAY: u32 = 3;\n\n    fn test_input_order(part: Part) -> Option<u32> {\n        Some(part.number())\n    }\n}\n\nfn other() {\n}\n";
+
+        assert_eq!(add_test_input_order(module).unwrap(), expected_module);
+        assert_eq!(add_test_input_order(expected_module).unwrap(), expected_module);
+    }
+
+    #[test]
+    fn test_import_examples() {
+        let directory = std::env::temp_dir().join(format!("aoc-2024-puzzle-{}", std::process::id()));
+        let source_directory = directory.join("src");
+        let data_directory = source_directory.join("dec_03");
+        let template = std::fs::read_to_string(crate_directory().join("src").join("dec_xx.rs")).unwrap();
+
+        std::fs::create_dir_all(&data_directory).unwrap();
+        std::fs::write(source_directory.join("dec_03.rs"), render_day_module(&template, 3).unwrap()).unwrap();
+        std::fs::write(data_directory.join("test_data.txt"), "").unwrap();
+
+        // Before part two is unlocked, the only example is shared by both parts
+        let changed_files = import_examples(&directory, 3, &first_part_only(PAGE)).unwrap();
+
+        assert_eq!(changed_files.len(), 3);
+        assert_eq!(std::fs::read_to_string(data_directory.join("test_data.txt")).unwrap(), "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)\n");
+        assert!(std::fs::read_to_string(directory.join(ANSWERS_FILE_NAME)).unwrap().contains("[dec_03]\npart_1.test = 161\n"));
+        assert!(import_examples(&directory, 3, &first_part_only(PAGE)).unwrap().is_empty());
+
+        // Switching to an example per part replaces the shared example instead of leaving it behind
+        let changed_files = import_examples(&directory, 3, PAGE).unwrap();
+        let module = std::fs::read_to_string(source_directory.join("dec_03.rs")).unwrap();
+
+        assert_eq!(changed_files.len(), 5);
+        assert!(changed_files.contains(&data_directory.join("test_data.txt")));
+        assert!(!data_directory.join("test_data.txt").exists());
+        assert!(data_directory.join("test_data_1.txt").is_file());
+        assert!(data_directory.join("test_data_2.txt").is_file());
+        assert!(module.contains("fn test_input_order(part: Part) -> Option<u32> {"));
+        assert!(module.contains("assert_eq!(run_second(false).unwrap(), 48);"));
+        assert!(import_examples(&directory, 3, PAGE).unwrap().is_empty());
+
+        // A different example is never overwritten
+        std::fs::write(data_directory.join("test_data_2.txt"), "edited").unwrap();
+
+        assert!(import_examples(&directory, 3, PAGE).is_err());
+        assert!(import_examples(&directory, 4, PAGE).is_err());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::{AocError, AocResult};
+use crate::report::ordinal;
+use crate::solution::Day;
+use crate::util::render::Canvas;
+use crate::{dec_01, dec_02, dec_03, dec_04, dec_05, dec_06, dec_07, dec_08, dec_09, dec_10, dec_11, dec_12, dec_13, dec_14, dec_15, dec_16};
+
+pub fn days() -> Vec<&'static dyn Day> {
+    vec![
+        &dec_01::Dec01,
+        &dec_02::Dec02,
+        &dec_03::Dec03,
+        &dec_04::Dec04,
+        &dec_05::Dec05,
+        &dec_06::Dec06,
+        &dec_07::Dec07,
+        &dec_08::Dec08,
+        &dec_09::Dec09,
+        &dec_10::Dec10,
+        &dec_11::Dec11,
+        &dec_12::Dec12,
+        &dec_13::Dec13,
+        &dec_14::Dec14,
+        &dec_15::Dec15,
+        &dec_16::Dec16,
+    ]
+}
+
+pub fn find_day(day: u32) -> Option<&'static dyn Day> {
+    days().into_iter().find(|d| d.day() == day)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    Latest,
+    Single(u32),
+    All,
+}
+
+pub fn find_solved_day(day: u32) -> AocResult<&'static dyn Day> {
+    find_day(day).ok_or(AocError::unsolvable(format!("December {} has not been solved yet", ordinal(day))))
+}
+
+pub fn select_days(day_selection: &DaySelection) -> AocResult<Vec<&'static dyn Day>> {
+    match day_selection {
+        DaySelection::All => Ok(days()),
+        DaySelection::Latest => days()
+            .last()
+            .map(|d| vec![*d])
+            .ok_or(AocError::unsolvable("No days have been registered")),
+        DaySelection::Single(number) => Ok(vec![find_solved_day(*number)?]),
+    }
+}
+
+// Days that can draw their solution
+pub fn render_day(day: u32, text: &str) -> AocResult<Canvas> {
+    match day {
+        16 => dec_16::render_optimal_route(text),
+        _ => Err(AocError::unsolvable(format!("December {} has nothing to render", ordinal(day)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_days() {
+        assert_eq!(select_days(&DaySelection::All).unwrap().len(), days().len());
+        assert_eq!(select_days(&DaySelection::Latest).unwrap()[0].day(), days().last().unwrap().day());
+        assert_eq!(select_days(&DaySelection::Single(7)).unwrap()[0].day(), 7);
+        assert!(matches!(select_days(&DaySelection::Single(25)), Err(AocError::Unsolvable(_))));
+    }
+
+    #[test]
+    fn test_render_day() {
+        assert!(render_day(16, "###\n#S#\n#E#\n###").is_ok());
+        assert!(render_day(3, "mul(2,4)").is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use itertools::Itertools;
+
+use crate::answers::AnswerStatus;
+use crate::benchmark::format_duration;
+use crate::input::InputSource;
+use crate::runner::PartResult;
+use crate::solution::Answer;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+pub enum Alignment {
+    Left,
+    Right,
+}
+
+const CSV_HEADER: &str = "day,part,input,answer,expected,duration_us,status,error";
+
+impl OutputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+pub fn ordinal(n: u32) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+
+    format!("{}{}", n, suffix)
+}
+
+// Lays out the rows in columns separated by two spaces, the header is the first row
+pub fn format_table(header: &[&str], rows: &[Vec<String>], alignments: &[Alignment]) -> String {
+    let all_rows = std::iter::once(header.iter().map(|h| h.to_string()).collect::<Vec<String>>())
+        .chain(rows.iter().cloned())
+        .collect::<Vec<Vec<String>>>();
+
+    let column_widths = (0..header.len())
+        .map(|column| all_rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<usize>>();
+
+    all_rows
+        .iter()
+        .map(|row| {
+            row
+                .iter()
+                .zip(column_widths.iter())
+                .zip(alignments.iter())
+                .map(|((cell, width), alignment)| match alignment {
+                    Alignment::Left => format!("{:<width$}", cell, width = width),
+                    Alignment::Right => format!("{:>width$}", cell, width = width),
+                })
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .join("\n")
+}
+
+pub fn format_results(results: &[PartResult], format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Text => format_results_table(results),
+        OutputFormat::Json => format_results_json(results),
+        OutputFormat::Csv => format_results_csv(results),
+    }
+}
+
+pub fn format_results_table(results: &[PartResult]) -> String {
+    let rows = results
+        .iter()
+        .map(|result| {
+            vec![
+                result.day.to_string(),
+                result.part.number().to_string(),
+                optional_answer(result.answer.as_ref().ok().copied()).unwrap_or("-".to_string()),
+                optional_answer(result.expected).unwrap_or("-".to_string()),
+                format_duration(result.duration),
+                status_name(result).to_uppercase(),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    format_table(
+        &["Day", "Part", "Answer", "Expected", "Time", "Status"],
+        &rows,
+        &[Alignment::Left, Alignment::Left, Alignment::Right, Alignment::Right, Alignment::Right, Alignment::Left],
+    )
+}
+
+pub fn format_results_json(results: &[PartResult]) -> String {
+    let objects = results
+        .iter()
+        .map(|result| {
+            let fields = [
+                ("day", result.day.to_string()),
+                ("part", result.part.number().to_string()),
+                ("input", json_string(input_kind(&result.source))),
+                ("answer", optional_answer(result.answer.as_ref().ok().copied()).unwrap_or("null".to_string())),
+                ("expected", optional_answer(result.expected).unwrap_or("null".to_string())),
+                ("duration_us", result.duration.as_micros().to_string()),
+                ("status", json_string(status_name(result))),
+                ("error", error_message(result).map(|e| json_string(&e)).unwrap_or("null".to_string())),
+            ];
+
+            format!("  {{{}}}", fields.iter().map(|(name, value)| format!("\"{}\": {}", name, value)).join(", "))
+        })
+        .collect::<Vec<String>>();
+
+    match objects.is_empty() {
+        true => "[]".to_string(),
+        false => format!("[\n{}\n]", objects.join(",\n")),
+    }
+}
+
+pub fn format_results_csv(results: &[PartResult]) -> String {
+    std::iter::once(CSV_HEADER.to_string())
+        .chain(results.iter().map(|result| {
+            [
+                result.day.to_string(),
+                result.part.number().to_string(),
+                input_kind(&result.source).to_string(),
+                optional_answer(result.answer.as_ref().ok().copied()).unwrap_or_default(),
+                optional_answer(result.expected).unwrap_or_default(),
+                result.duration.as_micros().to_string(),
+                status_name(result).to_string(),
+                error_message(result).map(|e| csv_field(&e)).unwrap_or_default(),
+            ]
+            .join(",")
+        }))
+        .join("\n")
+}
+
+fn status_name(result: &PartResult) -> &'static str {
+    match result.status() {
+        Some(AnswerStatus::Pass) => "pass",
+        Some(AnswerStatus::Fail { .. }) => "fail",
+        Some(AnswerStatus::Unknown) => "unknown",
+        None => "error",
+    }
+}
+
+fn input_kind(source: &InputSource) -> &'static str {
+    match source {
+        InputSource::Data { is_real: true, .. } => "real",
+        InputSource::Data { is_real: false, .. } => "test",
+        InputSource::File(_) => "file",
+        InputSource::Stdin => "stdin",
+    }
+}
+
+fn optional_answer(answer: Option<Answer>) -> Option<String> {
+    answer.map(|a| a.to_string())
+}
+
+fn error_message(result: &PartResult) -> Option<String> {
+    result.answer.as_ref().err().map(|e| e.to_string())
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    match s.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+    use crate::solution::Part;
+    use std::time::Duration;
+    use test_case::test_case;
+
+    #[test_case(1, "1st")]
+    #[test_case(2, "2nd")]
+    #[test_case(3, "3rd")]
+    #[test_case(11, "11th")]
+    #[test_case(16, "16th")]
+    #[test_case(22, "22nd")]
+    fn test_ordinal(n: u32, expected_result: &str) {
+        assert_eq!(ordinal(n), expected_result);
+    }
+
+    fn results() -> Vec<PartResult> {
+        vec![
+            PartResult {
+                day: 1,
+                part: Part::Two,
+                source: InputSource::Data { is_real: false, day: 1, order: None },
+                answer: Ok(31),
+                expected: Some(30),
+                duration: Duration::from_micros(250),
+            },
+            PartResult {
+                day: 3,
+                part: Part::One,
+                source: InputSource::Stdin,
+                answer: Err(AocError::parse("Bad \"mul\", again")),
+                expected: None,
+                duration: Duration::from_micros(12),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = vec![
+            vec!["1".to_string(), "11".to_string()],
+            vec!["12".to_string(), "2756096".to_string()],
+        ];
+
+        let expected_table = "\
+Day   Answer
+1         11
+12   2756096";
+
+        assert_eq!(format_table(&["Day", "Answer"], &rows, &[Alignment::Left, Alignment::Right]), expected_table);
+    }
+
+    #[test]
+    fn test_format_results_table() {
+        let expected_table = "\
+Day  Part  Answer  Expected      Time  Status
+1    2         31        30  250.00µs  FAIL
+3    1          -         -   12.00µs  ERROR";
+
+        assert_eq!(format_results(&results(), OutputFormat::Text), expected_table);
+    }
+
+    #[test]
+    fn test_format_results_json() {
+        let expected_json = r#"[
+  {"day": 1, "part": 2, "input": "test", "answer": 31, "expected": 30, "duration_us": 250, "status": "fail", "error": null},
+  {"day": 3, "part": 1, "input": "stdin", "answer": null, "expected": null, "duration_us": 12, "status": "error", "error": "Parse error: Bad \"mul\", again"}
+]"#;
+
+        assert_eq!(format_results(&results(), OutputFormat::Json), expected_json);
+    }
+
+    #[test]
+    fn test_format_results_csv() {
+        let expected_csv = "\
+day,part,input,answer,expected,duration_us,status,error
+1,2,test,31,30,250,fail,
+3,1,stdin,,,12,error,\"Parse error: Bad \"\"mul\"\", again\"";
+
+        assert_eq!(format_results(&results(), OutputFormat::Csv), expected_csv);
+    }
+
+    #[test]
+    fn test_format_no_results_as_json() {
+        assert_eq!(format_results(&[], OutputFormat::Json), "[]");
+    }
+
+    #[test_case("text", Some(OutputFormat::Text))]
+    #[test_case("json", Some(OutputFormat::Json))]
+    #[test_case("csv", Some(OutputFormat::Csv))]
+    #[test_case("xml", None)]
+    fn test_output_format_from_name(name: &str, expected_format: Option<OutputFormat>) {
+        assert_eq!(OutputFormat::from_name(name), expected_format);
+    }
+}
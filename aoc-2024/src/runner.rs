@@ -0,0 +1,252 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::answers::{answer_key_for_source, AnswerStatus, AnswerStore};
+use crate::benchmark::time;
+use crate::error::AocResult;
+use crate::input::{InputCache, InputError, InputSelection, InputSource};
+use crate::solution::{Answer, Day, Part};
+
+// A part of a day and the input it is going to be solved for
+#[derive(Clone)]
+pub struct PartSelection {
+    pub day: &'static dyn Day,
+    pub part: Part,
+    pub source: InputSource,
+}
+
+pub struct PartRun<'a> {
+    pub day: &'a dyn Day,
+    pub part: Part,
+    pub source: InputSource,
+    pub text: &'a str,
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: Part,
+    pub source: InputSource,
+    pub answer: AocResult<Answer>,
+    pub expected: Option<Answer>,
+    pub duration: Duration,
+}
+
+impl PartResult {
+    pub fn status(&self) -> Option<AnswerStatus> {
+        let answer = *self.answer.as_ref().ok()?;
+
+        Some(AnswerStatus::compare(answer, self.expected))
+    }
+
+    pub fn is_failure(&self) -> bool {
+        !matches!(self.status(), Some(AnswerStatus::Pass | AnswerStatus::Unknown))
+    }
+}
+
+// A part whose input could not be read is reported like any other error, so it shows up in every report
+pub fn unread_part(selection: &PartSelection, error: InputError, answer_store: &AnswerStore) -> PartResult {
+    let expected = answer_key_for_source(selection.day, selection.part, &selection.source)
+        .and_then(|key| answer_store.get(&key));
+
+    PartResult {
+        day: selection.day.day(),
+        part: selection.part,
+        source: selection.source.clone(),
+        answer: Err(error.into()),
+        expected,
+        duration: Duration::ZERO,
+    }
+}
+
+pub fn run_part(part_run: &PartRun, answer_store: &AnswerStore) -> PartResult {
+    let (answer, duration) = time(|| part_run.day.solve(part_run.part, part_run.text));
+
+    let expected = answer_key_for_source(part_run.day, part_run.part, &part_run.source)
+        .and_then(|key| answer_store.get(&key));
+
+    PartResult {
+        day: part_run.day.day(),
+        part: part_run.part,
+        source: part_run.source.clone(),
+        answer,
+        expected,
+        duration,
+    }
+}
+
+// Workers take the next unclaimed part until none are left, results come back in the order of the runs
+pub fn run_parts_in_parallel(part_runs: &[PartRun], answer_store: &AnswerStore, thread_count: usize) -> Vec<PartResult> {
+    let next_index = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..thread_count.max(1) {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+
+                let Some(part_run) = part_runs.get(index) else {
+                    break;
+                };
+
+                let result = run_part(part_run, answer_store);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+pub fn select_parts(
+    days: &[&'static dyn Day],
+    parts: &[Part],
+    input: &InputSelection,
+    order: Option<u32>,
+) -> Vec<PartSelection> {
+    days
+        .iter()
+        .flat_map(|day| parts.iter().map(move |part| PartSelection { day: *day, part: *part, source: input.source_for(*day, *part, order) }))
+        .collect()
+}
+
+// Solves the parts one after another, each result is handed over as soon as it is ready
+pub fn run_in_sequence(
+    selections: &[PartSelection],
+    inputs: &mut InputCache,
+    answer_store: &AnswerStore,
+    mut on_result: impl FnMut(PartResult),
+) {
+    for selection in selections {
+        let result = match inputs.read(&selection.source) {
+            Ok(text) => {
+                let part_run = PartRun { day: selection.day, part: selection.part, source: selection.source.clone(), text };
+
+                run_part(&part_run, answer_store)
+            },
+            Err(error) => unread_part(selection, error, answer_store),
+        };
+
+        on_result(result);
+    }
+}
+
+// Inputs are read up front, stdin included, so the workers only have to solve.
+// Results come back in the order of the selections.
+pub fn run_in_parallel(
+    selections: &[PartSelection],
+    inputs: &mut InputCache,
+    answer_store: &AnswerStore,
+    thread_count: usize,
+) -> Vec<PartResult> {
+    let mut readable_selections = Vec::new();
+    let mut results = Vec::new();
+
+    for (index, selection) in selections.iter().enumerate() {
+        match inputs.read(&selection.source) {
+            Ok(_) => readable_selections.push((index, selection)),
+            Err(error) => results.push((index, unread_part(selection, error, answer_store))),
+        }
+    }
+
+    let part_runs = readable_selections
+        .iter()
+        .map(|(_, selection)| PartRun {
+            day: selection.day,
+            part: selection.part,
+            source: selection.source.clone(),
+            text: inputs.get(&selection.source).unwrap_or_default(),
+        })
+        .collect::<Vec<PartRun>>();
+
+    let solved_results = run_parts_in_parallel(&part_runs, answer_store, thread_count);
+
+    results.extend(readable_selections.iter().map(|(index, _)| *index).zip(solved_results));
+    results.sort_by_key(|(index, _)| *index);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+pub fn default_thread_count() -> usize {
+    std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AocError;
+
+    const DEC_01_TEST_DATA: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+
+    fn answer_store() -> AnswerStore {
+        AnswerStore::parse("[dec_01]\npart_1.test = 11\npart_2.test = 30\n").unwrap()
+    }
+
+    fn part_runs(day: &dyn Day) -> Vec<PartRun<'_>> {
+        [Part::One, Part::Two, Part::One]
+            .into_iter()
+            .map(|part| PartRun {
+                day,
+                part,
+                source: InputSource::Data { is_real: false, day: 1, order: None },
+                text: DEC_01_TEST_DATA,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_run_part_compares_with_store() {
+        let day = crate::registry::find_day(1).unwrap();
+        let part_runs = part_runs(day);
+
+        let first_result = run_part(&part_runs[0], &answer_store());
+        let second_result = run_part(&part_runs[1], &answer_store());
+
+        assert_eq!(first_result.status(), Some(AnswerStatus::Pass));
+        assert_eq!(second_result.status(), Some(AnswerStatus::Fail { expected: 30 }));
+        assert!(second_result.is_failure());
+    }
+
+    #[test]
+    fn test_run_parts_in_parallel_keeps_order() {
+        let day = crate::registry::find_day(1).unwrap();
+
+        let results = run_parts_in_parallel(&part_runs(day), &answer_store(), 4);
+
+        let answers = results.iter().map(|r| (r.part, *r.answer.as_ref().unwrap())).collect::<Vec<_>>();
+        assert_eq!(answers, vec![(Part::One, 11), (Part::Two, 31), (Part::One, 11)]);
+    }
+
+    #[test]
+    fn test_unread_inputs_are_error_results() {
+        let day = crate::registry::find_day(1).unwrap();
+        let mut selections = select_parts(&[day], &[Part::One, Part::Two], &InputSelection::Test, None);
+        selections.insert(1, PartSelection { day, part: Part::Two, source: InputSource::File("/nowhere/input.txt".into()) });
+
+        let results = run_in_parallel(&selections, &mut InputCache::new(), &answer_store(), 2);
+
+        assert_eq!(results.iter().map(|r| r.answer.is_ok()).collect::<Vec<bool>>(), vec![true, false, true]);
+        assert!(matches!(results[1].answer, Err(AocError::Io(_))));
+        assert!(results[1].is_failure());
+
+        let mut sequence = Vec::new();
+        run_in_sequence(&selections, &mut InputCache::new(), &answer_store(), |result| sequence.push(result.answer.is_ok()));
+
+        assert_eq!(sequence, vec![true, false, true]);
+    }
+
+    #[test]
+    fn test_errors_are_failures() {
+        let day = crate::registry::find_day(1).unwrap();
+        let part_run = PartRun { day, part: Part::One, source: InputSource::Stdin, text: "1 x" };
+
+        let result = run_part(&part_run, &answer_store());
+
+        assert_eq!(result.status(), None);
+        assert!(result.is_failure());
+    }
+}
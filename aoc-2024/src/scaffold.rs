@@ -0,0 +1,327 @@
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::error::{AocError, AocResult};
+use crate::input::{data_file_name, day_folder_name, InputError};
+
+const TEMPLATE_FILE_NAME: &str = "dec_xx.rs";
+const TEMPLATE_STRUCT_NAME: &str = "DecXx";
+const TEMPLATE_DAY_CONSTANT: &str = "const DAY: u32 = 0;";
+
+const LIBRARY_FILE: &str = "src/lib.rs";
+const REGISTRY_FILE: &str = "src/registry.rs";
+
+pub fn day_struct_name(day: u32) -> String {
+    format!("Dec{:02}", day)
+}
+
+pub fn crate_directory() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+// Creates src/dec_NN.rs and its data folder from the template, then registers the day.
+// Returns the files that were created or changed.
+pub fn create_day(crate_directory: &Path, day: u32) -> AocResult<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        return Err(AocError::scaffold(format!("Days go from 1 to 25, got {}", day)));
+    }
+
+    let source_directory = crate_directory.join("src");
+    let module_path = source_directory.join(format!("{}.rs", day_folder_name(day)));
+    let data_directory = source_directory.join(day_folder_name(day));
+
+    for path in [&module_path, &data_directory] {
+        if path.exists() {
+            return Err(AocError::scaffold(format!("{} already exists", path.display())));
+        }
+    }
+
+    let template = read_file(&source_directory.join(TEMPLATE_FILE_NAME))?;
+    let module = render_day_module(&template, day)?;
+
+    // Every file keeps its previous text, so the ones already written can be put back when a later write fails
+    let mut changed_files = vec![(module_path, module, None)];
+
+    let library_path = crate_directory.join(LIBRARY_FILE);
+    let library_text = read_file(&library_path)?;
+    let library = register_module_declaration(&library_text, day)?;
+    changed_files.push((library_path, library, Some(library_text)));
+
+    let registry_path = crate_directory.join(REGISTRY_FILE);
+    let registry_text = read_file(&registry_path)?;
+    let registry = register_in_registry(&registry_text, day)?;
+    changed_files.push((registry_path, registry, Some(registry_text)));
+
+    std::fs::create_dir_all(&data_directory)
+        .map_err(|error| InputError::Unwritable { path: data_directory.clone(), error })?;
+
+    for is_real in [true, false] {
+        changed_files.push((data_directory.join(data_file_name(is_real, None)), String::new(), None));
+    }
+
+    if let Err(error) = write_files(&changed_files) {
+        let _ = std::fs::remove_dir(&data_directory);
+        return Err(error);
+    }
+
+    Ok(changed_files.into_iter().map(|(path, _, _)| path).collect())
+}
+
+// Writes the files in order. On failure the written ones get their previous text back, new ones are removed.
+fn write_files(files: &[(PathBuf, String, Option<String>)]) -> AocResult<()> {
+    for (index, (path, text, _)) in files.iter().enumerate() {
+        if let Err(error) = std::fs::write(path, text) {
+            for (written_path, _, previous_text) in files[..index].iter().rev() {
+                let _ = match previous_text {
+                    Some(previous_text) => std::fs::write(written_path, previous_text),
+                    None => std::fs::remove_file(written_path),
+                };
+            }
+
+            return Err(InputError::Unwritable { path: path.clone(), error }.into());
+        }
+    }
+
+    Ok(())
+}
+
+pub fn render_day_module(template: &str, day: u32) -> AocResult<String> {
+    if !template.contains(TEMPLATE_STRUCT_NAME) || !template.contains(TEMPLATE_DAY_CONSTANT) {
+        return Err(AocError::scaffold(format!(
+            "The template should contain {} and {}",
+            TEMPLATE_STRUCT_NAME,
+            TEMPLATE_DAY_CONSTANT,
+        )));
+    }
+
+    Ok(template
+        .replace(TEMPLATE_STRUCT_NAME, &day_struct_name(day))
+        .replace(TEMPLATE_DAY_CONSTANT, &format!("const DAY: u32 = {};", day)))
+}
+
+// Copies the declaration of the closest day, attributes included
+pub fn register_module_declaration(text: &str, day: u32) -> AocResult<String> {
+    let declaration = Regex::new(r"^(?:pub )?mod dec_(\d+);$").unwrap();
+    let lines = text.lines().collect::<Vec<&str>>();
+
+    let declared_days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_in_line(&declaration, line).map(|d| (index, d)))
+        .collect::<Vec<(usize, u32)>>();
+
+    let (insert_index, neighbour_index, neighbour_day) = closest_day(&declared_days, day)?;
+
+    let block_start = (0..neighbour_index)
+        .rev()
+        .take_while(|index| lines[*index].starts_with("#["))
+        .last()
+        .unwrap_or(neighbour_index);
+
+    let new_block = lines[block_start..=neighbour_index]
+        .iter()
+        .map(|line| line.replace(&day_folder_name(neighbour_day), &day_folder_name(day)))
+        .collect::<Vec<String>>();
+
+    let insert_index = match insert_index == neighbour_index {
+        true => block_start,
+        false => insert_index,
+    };
+
+    Ok(insert_lines(&lines, insert_index, new_block, text.ends_with('\n')))
+}
+
+pub fn register_in_registry(text: &str, day: u32) -> AocResult<String> {
+    let use_statement = Regex::new(r"^use crate::\{(dec_\d+(, dec_\d+)*)\};$").unwrap();
+    let entry = Regex::new(r"^\s*&dec_(\d+)::Dec\d+,$").unwrap();
+
+    let mut lines = text.lines().map(|l| l.to_string()).collect::<Vec<String>>();
+
+    let Some(use_index) = lines.iter().position(|l| use_statement.is_match(l)) else {
+        return Err(AocError::scaffold("Could not find the use statement for the day modules"));
+    };
+
+    let modules = use_statement.captures(&lines[use_index]).unwrap()[1]
+        .split(", ")
+        .map(|m| m.to_string())
+        .chain(std::iter::once(day_folder_name(day)))
+        .sorted()
+        .join(", ");
+    lines[use_index] = format!("use crate::{{{}}};", modules);
+
+    let registered_days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| day_in_line(&entry, line).map(|d| (index, d)))
+        .collect::<Vec<(usize, u32)>>();
+
+    let (insert_index, neighbour_index, _) = closest_day(&registered_days, day)?;
+    let indentation = lines[neighbour_index].chars().take_while(|c| c.is_whitespace()).collect::<String>();
+    let new_entry = format!("{}&{}::{},", indentation, day_folder_name(day), day_struct_name(day));
+
+    let lines = lines.iter().map(|l| l.as_str()).collect::<Vec<&str>>();
+
+    Ok(insert_lines(&lines, insert_index, vec![new_entry], text.ends_with('\n')))
+}
+
+fn day_in_line(pattern: &Regex, line: &str) -> Option<u32> {
+    pattern.captures(line).and_then(|c| c[1].parse::<u32>().ok())
+}
+
+// Returns where the new day goes, together with the line and number of the day it is modelled on
+fn closest_day(declared_days: &[(usize, u32)], day: u32) -> AocResult<(usize, usize, u32)> {
+    if declared_days.iter().any(|(_, d)| *d == day) {
+        return Err(AocError::scaffold(format!("{} is already registered", day_folder_name(day))));
+    }
+
+    match declared_days.iter().rev().find(|(_, d)| *d < day) {
+        Some((index, neighbour_day)) => Ok((index + 1, *index, *neighbour_day)),
+        None => match declared_days.first() {
+            Some((index, neighbour_day)) => Ok((*index, *index, *neighbour_day)),
+            None => Err(AocError::scaffold("Could not find any registered day to follow")),
+        },
+    }
+}
+
+fn insert_lines(lines: &[&str], index: usize, new_lines: Vec<String>, ends_with_newline: bool) -> String {
+    let mut text = lines[..index]
+        .iter()
+        .map(|l| l.to_string())
+        .chain(new_lines)
+        .chain(lines[index..].iter().map(|l| l.to_string()))
+        .join("\n");
+
+    if ends_with_newline {
+        text.push('\n');
+    }
+
+    text
+}
+
+fn read_file(path: &Path) -> AocResult<String> {
+    std::fs::read_to_string(path).map_err(|error| InputError::Unreadable { path: path.to_path_buf(), error }.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIBRARY: &str = "\
+pub mod benchmark;
+
+#[allow(dead_code)]
+pub mod dec_01;
+#[allow(dead_code)]
+pub mod dec_03;
+
+pub mod error;
+";
+
+    const REGISTRY: &str = "\
+use crate::solution::Day;
+use crate::{dec_01, dec_03};
+
+pub fn days() -> Vec<&'static dyn Day> {
+    vec![
+        &dec_01::Dec01,
+        &dec_03::Dec03,
+    ]
+}
+";
+
+    #[test]
+    fn test_render_day_module() {
+        let template = "pub struct DecXx;\n\nimpl Solution for DecXx {\n    const DAY: u32 = 0;\n}\n";
+
+        let expected_module = "pub struct Dec07;\n\nimpl Solution for Dec07 {\n    const DAY: u32 = 7;\n}\n";
+
+        assert_eq!(render_day_module(template, 7).unwrap(), expected_module);
+    }
+
+    #[test]
+    fn test_register_module_declaration_in_order() {
+        let expected_library = LIBRARY.replace(
+            "pub mod dec_01;\n#[allow(dead_code)]\npub mod dec_03;",
+            "pub mod dec_01;\n#[allow(dead_code)]\npub mod dec_02;\n#[allow(dead_code)]\npub mod dec_03;",
+        );
+
+        assert_eq!(register_module_declaration(LIBRARY, 2).unwrap(), expected_library);
+    }
+
+    #[test]
+    fn test_register_module_declaration_before_first_day() {
+        let library = "pub mod benchmark;\n#[allow(dead_code)]\npub mod dec_05;\n";
+
+        let expected_library = "pub mod benchmark;\n#[allow(dead_code)]\npub mod dec_02;\n#[allow(dead_code)]\npub mod dec_05;\n";
+
+        assert_eq!(register_module_declaration(library, 2).unwrap(), expected_library);
+    }
+
+    #[test]
+    fn test_register_in_registry() {
+        let expected_registry = REGISTRY
+            .replace("use crate::{dec_01, dec_03};", "use crate::{dec_01, dec_03, dec_04};")
+            .replace("        &dec_03::Dec03,\n", "        &dec_03::Dec03,\n        &dec_04::Dec04,\n");
+
+        assert_eq!(register_in_registry(REGISTRY, 4).unwrap(), expected_registry);
+    }
+
+    #[test]
+    fn test_refuses_registered_day() {
+        assert!(register_module_declaration(LIBRARY, 3).is_err());
+        assert!(register_in_registry(REGISTRY, 1).is_err());
+    }
+
+    #[test]
+    fn test_write_files_restores_on_failure() {
+        let directory = std::env::temp_dir().join(format!("aoc-2024-write-files-{}", std::process::id()));
+        let existing_path = directory.join("lib.rs");
+        let new_path = directory.join("dec_02.rs");
+
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(&existing_path, LIBRARY).unwrap();
+
+        let files = vec![
+            (new_path.clone(), "pub struct Dec02;".to_string(), None),
+            (existing_path.clone(), "changed".to_string(), Some(LIBRARY.to_string())),
+            (directory.join("missing").join("real_data.txt"), String::new(), None),
+        ];
+
+        assert!(write_files(&files).is_err());
+        assert!(!new_path.exists());
+        assert_eq!(std::fs::read_to_string(&existing_path).unwrap(), LIBRARY);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_create_day() {
+        let directory = std::env::temp_dir().join(format!("aoc-2024-scaffold-{}", std::process::id()));
+        let source_directory = directory.join("src");
+        let template = std::fs::read_to_string(crate_directory().join("src").join(TEMPLATE_FILE_NAME)).unwrap();
+
+        std::fs::create_dir_all(&source_directory).unwrap();
+        std::fs::write(source_directory.join("lib.rs"), LIBRARY).unwrap();
+        std::fs::write(source_directory.join("registry.rs"), REGISTRY).unwrap();
+        std::fs::write(source_directory.join(TEMPLATE_FILE_NAME), template).unwrap();
+
+        let changed_files = create_day(&directory, 2).unwrap();
+
+        assert_eq!(changed_files.len(), 5);
+        assert!(std::fs::read_to_string(source_directory.join("dec_02.rs")).unwrap().contains("pub struct Dec02;"));
+        assert!(source_directory.join("dec_02").join("real_data.txt").is_file());
+        assert!(source_directory.join("dec_02").join("test_data.txt").is_file());
+        assert!(std::fs::read_to_string(source_directory.join("lib.rs")).unwrap().contains("pub mod dec_02;"));
+        assert!(std::fs::read_to_string(source_directory.join("registry.rs")).unwrap().contains("&dec_02::Dec02,"));
+
+        // A second attempt must not touch the existing day
+        std::fs::write(source_directory.join("dec_02.rs"), "solved").unwrap();
+
+        assert!(create_day(&directory, 2).is_err());
+        assert_eq!(std::fs::read_to_string(source_directory.join("dec_02.rs")).unwrap(), "solved");
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,93 @@
+use crate::benchmark::{time, Timing};
+use crate::error::AocResult;
+use crate::util::read_single_string_from_file;
+
+pub type Answer = i64;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(&self) -> u32 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+pub fn parts() -> Vec<Part> {
+    vec![Part::One, Part::Two]
+}
+
+pub trait Solution {
+    type Input;
+
+    const DAY: u32;
+
+    fn parse(&self, text: &str) -> AocResult<Self::Input>;
+
+    fn part_one(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    fn part_two(&self, input: &Self::Input) -> AocResult<Answer>;
+
+    // Some days come with a separate example for each part, stored as test_data_1.txt, test_data_2.txt, ...
+    fn test_input_order(_part: Part) -> Option<u32> {
+        None
+    }
+}
+
+// Object safe counterpart of `Solution`, so days with different input types can live in the same registry
+pub trait Day: Sync {
+    fn day(&self) -> u32;
+
+    fn input_order(&self, is_real: bool, part: Part) -> Option<u32>;
+
+    fn solve(&self, part: Part, text: &str) -> AocResult<Answer>;
+
+    fn solve_timed(&self, part: Part, text: &str) -> AocResult<(Answer, Timing)>;
+
+    fn run(&self, part: Part, is_real: bool) -> AocResult<Answer> {
+        let order = self.input_order(is_real, part);
+        let text = read_single_string_from_file(is_real, self.day(), order)?;
+
+        self.solve(part, &text)
+    }
+}
+
+impl<S> Day for S where S: Solution + Sync {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn input_order(&self, is_real: bool, part: Part) -> Option<u32> {
+        match is_real {
+            true => None,
+            false => S::test_input_order(part),
+        }
+    }
+
+    fn solve(&self, part: Part, text: &str) -> AocResult<Answer> {
+        let input = self.parse(text)?;
+
+        match part {
+            Part::One => self.part_one(&input),
+            Part::Two => self.part_two(&input),
+        }
+    }
+
+    fn solve_timed(&self, part: Part, text: &str) -> AocResult<(Answer, Timing)> {
+        let (input, parse) = time(|| self.parse(text));
+        let input = input?;
+
+        let (answer, solve) = time(|| match part {
+            Part::One => self.part_one(&input),
+            Part::Two => self.part_two(&input),
+        });
+
+        Ok((answer?, Timing { parse, solve }))
+    }
+}
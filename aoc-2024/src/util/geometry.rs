@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use crate::util::{cardinal_directions, Direction, Point, Position, Vector};
+
+// A closed polygon along the edges of grid cells, vertices are cell corners and the
+// last vertex connects back to the first. The cells are always on the right hand side
+// when walking along the outline, so outer outlines run clockwise and holes anticlockwise
+// (with y pointing down).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Outline {
+    pub vertices: Vec<Point<i64>>,
+}
+
+impl Outline {
+    pub fn is_hole(&self) -> bool {
+        signed_double_area(&self.vertices) < 0
+    }
+
+    pub fn area(&self) -> i64 {
+        shoelace_area(&self.vertices)
+    }
+}
+
+// Any set of grid cells, connected or not
+#[derive(Clone, Debug)]
+pub struct CellShape {
+    cells: HashSet<Point<i64>>,
+}
+
+impl CellShape {
+    pub fn from_positions(positions: &[Position]) -> Self {
+        CellShape { cells: positions.iter().map(|p| Point::new(p.x as i64, p.y as i64)).collect() }
+    }
+
+    pub fn from_points(points: &[Point<i64>]) -> Self {
+        CellShape { cells: points.iter().copied().collect() }
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, point: &Point<i64>) -> bool {
+        self.cells.contains(point)
+    }
+
+    // Number of cell edges that border a cell outside the shape
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|c| cardinal_directions().iter().filter(|d| !self.contains(&(*c + step(d)))).count())
+            .sum()
+    }
+
+    // Every straight side ends in exactly one corner, so counting corners counts sides
+    pub fn number_of_sides(&self) -> usize {
+        let corner_directions = [
+            (Direction::N, Direction::E),
+            (Direction::E, Direction::S),
+            (Direction::S, Direction::W),
+            (Direction::W, Direction::N),
+        ];
+
+        self.cells
+            .iter()
+            .map(|c| {
+                corner_directions
+                    .iter()
+                    .filter(|(d1, d2)| {
+                        let first_side = self.contains(&(*c + step(d1)));
+                        let second_side = self.contains(&(*c + step(d2)));
+                        let diagonal = self.contains(&(*c + step(d1) + step(d2)));
+
+                        let is_outer_corner = !first_side && !second_side;
+                        let is_inner_corner = first_side && second_side && !diagonal;
+
+                        is_outer_corner || is_inner_corner
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    // One outline per boundary, outer boundaries as well as holes. Cells that only touch
+    // diagonally get separate outlines.
+    pub fn outlines(&self) -> Vec<Outline> {
+        let mut cells = self.cells.iter().copied().collect::<Vec<Point<i64>>>();
+        cells.sort_by_key(|c| (c.y, c.x));
+
+        let edges = cells
+            .iter()
+            .flat_map(|c| {
+                cardinal_directions()
+                    .into_iter()
+                    .filter(|d| !self.contains(&(*c + step(d))))
+                    .map(|d| boundary_edge(c, &d))
+                    .collect::<Vec<Edge>>()
+            })
+            .collect::<Vec<Edge>>();
+
+        let edge_set = edges.iter().copied().collect::<HashSet<Edge>>();
+        let mut used_edges = HashSet::new();
+        let mut outlines = Vec::new();
+
+        for first_edge in edges.iter() {
+            if used_edges.contains(first_edge) {
+                continue;
+            }
+
+            let mut vertices = Vec::new();
+            let mut edge = *first_edge;
+
+            loop {
+                used_edges.insert(edge);
+
+                let next_edge = next_boundary_edge(&edge, &edge_set);
+
+                if next_edge.travel != edge.travel {
+                    vertices.push(next_edge.start);
+                }
+
+                if next_edge == *first_edge {
+                    break;
+                }
+
+                edge = next_edge;
+            }
+
+            let first_vertex = (0..vertices.len()).min_by_key(|i| (vertices[*i].y, vertices[*i].x)).unwrap_or(0);
+            vertices.rotate_left(first_vertex);
+
+            outlines.push(Outline { vertices });
+        }
+
+        outlines
+    }
+
+    pub fn holes(&self) -> Vec<Outline> {
+        self.outlines().into_iter().filter(|o| o.is_hole()).collect()
+    }
+}
+
+// Area enclosed by a polygon, independent of the direction of the vertices.
+// Half units are rounded down, polygons along grid cell edges always have a whole area.
+pub fn shoelace_area(vertices: &[Point<i64>]) -> i64 {
+    signed_double_area(vertices).abs() / 2
+}
+
+// Positive for clockwise polygons when y points down
+fn signed_double_area(vertices: &[Point<i64>]) -> i64 {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct Edge {
+    start: Point<i64>,
+    travel: Direction,
+}
+
+fn step(direction: &Direction) -> Vector<i64> {
+    let movement = direction.get_movement();
+
+    Vector::new(movement.dx as i64, movement.dy as i64)
+}
+
+// The edge on the given side of a cell, oriented so that the cell is on its right
+fn boundary_edge(cell: &Point<i64>, side: &Direction) -> Edge {
+    let start = match side {
+        Direction::N => *cell,
+        Direction::E => *cell + Vector::new(1, 0),
+        Direction::S => *cell + Vector::new(1, 1),
+        _ => *cell + Vector::new(0, 1),
+    };
+
+    Edge { start, travel: side.turn_right() }
+}
+
+// Turning right first keeps diagonally touching cells on separate outlines
+fn next_boundary_edge(edge: &Edge, edge_set: &HashSet<Edge>) -> Edge {
+    let end = edge.start + step(&edge.travel);
+
+    [edge.travel.turn_right(), edge.travel, edge.travel.turn_left()]
+        .into_iter()
+        .map(|travel| Edge { start: end, travel })
+        .find(|e| edge_set.contains(e))
+        .expect("Boundary edges always form closed loops")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::split_lines;
+    use test_case::test_case;
+
+    fn shape(text: &str) -> CellShape {
+        let positions = split_lines(text)
+            .iter()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars().enumerate().filter(|(_, c)| *c == '#').map(move |(x, _)| Position { x, y }).collect::<Vec<_>>()
+            })
+            .collect::<Vec<Position>>();
+
+        CellShape::from_positions(&positions)
+    }
+
+    #[test_case("#", 4, 4)]
+    #[test_case("##\n##", 8, 4)]
+    #[test_case("###\n#..\n#..", 12, 6)]
+    #[test_case("###\n#.#\n###", 16, 8)]
+    #[test_case("#.\n.#", 8, 8)]
+    fn test_perimeter_and_sides(text: &str, expected_perimeter: usize, expected_sides: usize) {
+        let shape = shape(text);
+
+        assert_eq!(shape.perimeter(), expected_perimeter);
+        assert_eq!(shape.number_of_sides(), expected_sides);
+    }
+
+    #[test]
+    fn test_outline_of_l_shape() {
+        let outlines = shape("#.\n##").outlines();
+
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(
+            outlines[0].vertices,
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(1, 1), Point::new(2, 1), Point::new(2, 2), Point::new(0, 2)],
+        );
+        assert_eq!(outlines[0].area(), 3);
+        assert!(!outlines[0].is_hole());
+    }
+
+    #[test]
+    fn test_holes() {
+        let shape = shape("####\n#..#\n####");
+        let holes = shape.holes();
+
+        assert_eq!(shape.outlines().len(), 2);
+        assert_eq!(holes.len(), 1);
+        assert_eq!(holes[0].area(), 2);
+        assert_eq!(holes[0].vertices, vec![Point::new(1, 1), Point::new(1, 2), Point::new(3, 2), Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn test_diagonal_cells_get_separate_outlines() {
+        let outlines = shape("#.\n.#").outlines();
+
+        assert_eq!(outlines.len(), 2);
+        assert!(outlines.iter().all(|o| o.vertices.len() == 4 && o.area() == 1));
+    }
+
+    #[test]
+    fn test_outlines_agree_with_cells() {
+        let shape = shape("#####\n#.#.#\n#####\n..#..\n.###.");
+        let outlines = shape.outlines();
+
+        let vertex_count = outlines.iter().map(|o| o.vertices.len()).sum::<usize>();
+        let enclosed_area = outlines.iter().map(|o| if o.is_hole() { -o.area() } else { o.area() }).sum::<i64>();
+
+        assert_eq!(vertex_count, shape.number_of_sides());
+        assert_eq!(enclosed_area, shape.area() as i64);
+    }
+
+    #[test]
+    fn test_shoelace_area() {
+        let triangle = [Point::new(0, 0), Point::new(4, 0), Point::new(0, 3)];
+
+        assert_eq!(shoelace_area(&triangle), 6);
+        assert_eq!(shoelace_area(&triangle.iter().rev().copied().collect::<Vec<_>>()), 6);
+    }
+}
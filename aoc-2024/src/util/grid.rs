@@ -0,0 +1,251 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::{AocError, AocResult};
+use crate::util::{Dimensions, Direction, Position};
+
+// Row-major map of the puzzle input, position (x, y) is stored at y * width + x
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        match cells.len() == width * height {
+            true => Some(Grid { width, height, cells }),
+            false => None,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self where T: Clone {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    pub fn from_text_lines(lines: &[impl AsRef<str>], parse_from_char: fn(char) -> Option<T>) -> AocResult<Self> {
+        let width = lines.first().map(|l| l.as_ref().chars().count()).unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+
+        for (y, line) in lines.iter().map(|l| l.as_ref()).enumerate() {
+            let line_width = line.chars().count();
+
+            if line_width != width {
+                return Err(AocError::parse(format!("Expected {} characters, found {}", width, line_width)).at_line(y, line));
+            }
+
+            for (x, c) in line.chars().enumerate() {
+                let Some(value) = parse_from_char(c) else {
+                    return Err(AocError::parse(format!("Unexpected character '{}'", c)).at_column(x).at_line(y, line));
+                };
+
+                cells.push(value);
+            }
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        Dimensions { width: self.width, height: self.height }
+    }
+
+    pub fn includes(&self, position: &Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: &Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    // Returns the value that was replaced, or None when the position is outside the grid
+    pub fn set(&mut self, position: &Position, value: T) -> Option<T> {
+        self.get_mut(position).map(|cell| std::mem::replace(cell, value))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        match y < self.height {
+            true => Some(&self.cells[y * self.width..(y + 1) * self.width]),
+            false => None,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = match x < self.width {
+            true => &self.cells[x..],
+            false => &[],
+        };
+
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.cells.len()).map(move |i| Position { x: i % width, y: i / width })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter().find(|(_, v)| predicate(v)).map(|(p, _)| p)
+    }
+
+    pub fn positions_of(&self, value: &T) -> Vec<Position> where T: PartialEq {
+        self.iter().filter(|(_, v)| *v == value).map(|(p, _)| p).collect()
+    }
+
+    pub fn neighbours<'a>(
+        &'a self,
+        position: &'a Position,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Direction, Position, &'a T)> + 'a {
+        directions.iter().filter_map(move |direction| {
+            let neighbour = position.walk_in_direction(direction)?;
+
+            self.get(&neighbour).map(|v| (*direction, neighbour, v))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    fn index_of(&self, position: &Position) -> Option<usize> {
+        match self.includes(position) {
+            true => Some(position.y * self.width + position.x),
+            false => None,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(&position).unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(&position).unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{cardinal_directions, split_lines};
+
+    fn digit_grid() -> Grid<u32> {
+        Grid::from_text_lines(&split_lines("123\n456"), |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_from_text_lines() {
+        let grid = digit_grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(&Position { x: 2, y: 1 }), Some(&6));
+        assert_eq!(grid.get(&Position { x: 3, y: 0 }), None);
+        assert_eq!(grid.get(&Position { x: 0, y: 2 }), None);
+    }
+
+    #[test]
+    fn test_from_text_lines_rejects_ragged_rows() {
+        let Err(AocError::Parse(parse_error)) = Grid::from_text_lines(&split_lines("123\n45"), |c| c.to_digit(10)) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!(parse_error.line_number, Some(2));
+    }
+
+    #[test]
+    fn test_from_text_lines_reports_unexpected_character() {
+        let Err(AocError::Parse(parse_error)) = Grid::from_text_lines(&split_lines("123\n4x6"), |c| c.to_digit(10)) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!((parse_error.line_number, parse_error.column), (Some(2), Some(2)));
+    }
+
+    #[test]
+    fn test_get_mut_and_set() {
+        let mut grid = digit_grid();
+
+        *grid.get_mut(&Position { x: 0, y: 0 }).unwrap() = 9;
+
+        assert_eq!(grid.set(&Position { x: 1, y: 1 }, 0), Some(5));
+        assert_eq!(grid.set(&Position { x: 5, y: 5 }, 0), None);
+        assert_eq!(grid.rows().collect::<Vec<&[u32]>>(), vec![&[9, 2, 3][..], &[4, 0, 6][..]]);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = digit_grid();
+
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns().map(|c| c.copied().collect::<Vec<u32>>()).collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+        );
+    }
+
+    #[test]
+    fn test_find_and_positions_of() {
+        let grid = Grid::from_text_lines(&split_lines("#.#\n.#."), Some).unwrap();
+
+        assert_eq!(grid.find(|c| *c == '.'), Some(Position { x: 1, y: 0 }));
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+        assert_eq!(
+            grid.positions_of(&'#'),
+            vec![Position { x: 0, y: 0 }, Position { x: 2, y: 0 }, Position { x: 1, y: 1 }],
+        );
+    }
+
+    #[test]
+    fn test_neighbours_stay_inside() {
+        let grid = digit_grid();
+
+        let neighbours = grid
+            .neighbours(&Position { x: 0, y: 0 }, &cardinal_directions())
+            .map(|(_, _, v)| *v)
+            .collect::<Vec<u32>>();
+
+        assert_eq!(neighbours, vec![2, 4]);
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let grid: Grid<u32> = Grid::from_text_lines(&Vec::<String>::new(), |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.positions().count(), 0);
+    }
+}
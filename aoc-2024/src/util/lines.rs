@@ -0,0 +1,172 @@
+use crate::util::{greatest_common_divisor, Dimensions, Point, Position, Vector};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineExtent {
+    // Endless in both directions
+    Line,
+    // Starts at the first position and continues through the second one
+    Ray,
+    // From the first position to the second one
+    Segment,
+}
+
+// Every integer point on the line through the two positions that lies inside the dimensions,
+// ordered from the first position towards the second. Coinciding positions give at most one point.
+pub fn lattice_points(first: &Position, second: &Position, extent: LineExtent, dimensions: &Dimensions) -> Vec<Position> {
+    let start = Point::new(first.x as i64, first.y as i64);
+    let separation = Point::new(second.x as i64, second.y as i64) - start;
+
+    let number_of_steps = greatest_common_divisor(separation.dx.unsigned_abs() as usize, separation.dy.unsigned_abs() as usize);
+
+    if number_of_steps == 0 {
+        return match dimensions.includes(first) {
+            true => vec![*first],
+            false => Vec::new(),
+        };
+    }
+
+    let step = Vector::new(separation.dx / number_of_steps as i64, separation.dy / number_of_steps as i64);
+
+    let (extent_min, extent_max) = match extent {
+        LineExtent::Line => (i64::MIN, i64::MAX),
+        LineExtent::Ray => (0, i64::MAX),
+        LineExtent::Segment => (0, number_of_steps as i64),
+    };
+
+    let (x_min, x_max) = steps_inside(start.x, step.dx, dimensions.width as i64);
+    let (y_min, y_max) = steps_inside(start.y, step.dy, dimensions.height as i64);
+
+    let k_min = extent_min.max(x_min).max(y_min);
+    let k_max = extent_max.min(x_max).min(y_max);
+
+    (k_min..=k_max)
+        .filter_map(|k| (start + step * k).to_position())
+        .collect()
+}
+
+// Range of k for which start + k * step stays within 0..size, the range is empty when start > end
+fn steps_inside(start: i64, step: i64, size: i64) -> (i64, i64) {
+    match step {
+        0 if (0..size).contains(&start) => (i64::MIN, i64::MAX),
+        0 => (1, 0),
+        _ => {
+            let (lower_bound, upper_bound) = match step > 0 {
+                true => (-start, size - 1 - start),
+                false => (size - 1 - start, -start),
+            };
+
+            (ceiling_division(lower_bound, step), floor_division(upper_bound, step))
+        },
+    }
+}
+
+fn floor_division(a: i64, b: i64) -> i64 {
+    match b > 0 {
+        true => a.div_euclid(b),
+        false => (-a).div_euclid(-b),
+    }
+}
+
+fn ceiling_division(a: i64, b: i64) -> i64 {
+    -floor_division(-a, b)
+}
+
+// Cells crossed by a straight line between two points, both ends included, as drawn by Bresenham's algorithm
+pub fn bresenham_line(from: &Point<i64>, to: &Point<i64>) -> Vec<Point<i64>> {
+    let (dx, dy) = ((to.x - from.x).abs(), -(to.y - from.y).abs());
+    let (step_x, step_y) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+
+    let mut points = Vec::new();
+    let mut current = *from;
+    let mut error = dx + dy;
+
+    loop {
+        points.push(current);
+
+        if current == *to {
+            break;
+        }
+
+        let doubled_error = 2 * error;
+
+        if doubled_error >= dy {
+            error += dy;
+            current.x += step_x;
+        }
+
+        if doubled_error <= dx {
+            error += dx;
+            current.y += step_y;
+        }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(coordinates: &[(usize, usize)]) -> Vec<Position> {
+        coordinates.iter().map(|(x, y)| Position { x: *x, y: *y }).collect()
+    }
+
+    fn dimensions() -> Dimensions {
+        Dimensions { width: 10, height: 10 }
+    }
+
+    #[test]
+    fn test_segment_includes_points_in_between() {
+        let points = lattice_points(&Position { x: 1, y: 1 }, &Position { x: 3, y: 5 }, LineExtent::Segment, &dimensions());
+
+        assert_eq!(points, positions(&[(1, 1), (2, 3), (3, 5)]));
+    }
+
+    #[test]
+    fn test_ray() {
+        let points = lattice_points(&Position { x: 5, y: 4 }, &Position { x: 3, y: 5 }, LineExtent::Ray, &dimensions());
+
+        assert_eq!(points, positions(&[(5, 4), (3, 5), (1, 6)]));
+    }
+
+    #[test]
+    fn test_line_is_clipped() {
+        let points = lattice_points(&Position { x: 4, y: 3 }, &Position { x: 5, y: 5 }, LineExtent::Line, &dimensions());
+
+        assert_eq!(points, positions(&[(3, 1), (4, 3), (5, 5), (6, 7), (7, 9)]));
+    }
+
+    #[test]
+    fn test_axis_aligned_line() {
+        let points = lattice_points(&Position { x: 2, y: 7 }, &Position { x: 6, y: 7 }, LineExtent::Line, &Dimensions { width: 4, height: 8 });
+
+        assert_eq!(points, positions(&[(0, 7), (1, 7), (2, 7), (3, 7)]));
+    }
+
+    #[test]
+    fn test_start_outside_dimensions() {
+        let points = lattice_points(&Position { x: 12, y: 0 }, &Position { x: 11, y: 1 }, LineExtent::Ray, &dimensions());
+
+        assert_eq!(points.first(), Some(&Position { x: 9, y: 3 }));
+        assert_eq!(points.len(), 7);
+    }
+
+    #[test]
+    fn test_coinciding_positions() {
+        let position = Position { x: 1, y: 2 };
+
+        assert_eq!(lattice_points(&position, &position, LineExtent::Line, &dimensions()), vec![position]);
+    }
+
+    #[test]
+    fn test_bresenham_line() {
+        let points = bresenham_line(&Point::new(0, 0), &Point::new(5, 2));
+
+        assert_eq!(
+            points,
+            vec![Point::new(0, 0), Point::new(1, 0), Point::new(2, 1), Point::new(3, 1), Point::new(4, 2), Point::new(5, 2)],
+        );
+        assert_eq!(bresenham_line(&Point::new(2, 3), &Point::new(2, 0)).len(), 4);
+        assert_eq!(bresenham_line(&Point::new(1, 1), &Point::new(1, 1)), vec![Point::new(1, 1)]);
+    }
+}
@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use crate::error::{AocError, AocResult};
+use crate::input::{read_input, InputSource};
+
+pub mod geometry;
+mod grid;
+pub mod lines;
+pub mod parsing;
+mod point;
+pub mod regions;
+pub mod render;
+pub mod search;
+pub mod sections;
+pub mod torus;
+pub mod transform;
+
+pub use grid::Grid;
+pub use point::{Coordinate, Point, Vector};
+
+pub fn read_from_file(is_real: bool, day: u32, order: Option<u32>) -> AocResult<Vec<String>> {
+    let input = read_single_string_from_file(is_real, day, order)?;
+    
+    Ok(split_lines(&input))
+}
+
+pub fn split_lines(text: &str) -> Vec<String> {
+    text.lines().map(|s| s.to_string()).collect()
+}
+
+pub fn read_single_string_from_file(is_real: bool, day: u32, order: Option<u32>) -> AocResult<String> {
+    let source = InputSource::Data { is_real, day, order };
+
+    Ok(read_input(&source)?)
+}
+
+pub fn get_integers_in_string(s: &str) -> AocResult<Vec<i32>> {
+    get_integers(s)
+}
+
+// A minus sign directly in front of the digits makes the number negative, unless it follows
+// a letter or digit, so ranges like 1-3 are read as two positive numbers. Numbers that do not
+// fit the integer type are errors, which includes negative numbers when T is unsigned.
+pub fn get_integers<T>(s: &str) -> AocResult<Vec<T>>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    integer_spans(s)
+        .into_iter()
+        .map(|(start, end)| s[start..end]
+            .parse::<T>()
+            .map_err(|e| AocError::parse(format!("Invalid number {}: {}", &s[start..end], e)).at_column(start))
+        )
+        .collect()
+}
+
+// For lines with a known layout, like p=0,4 v=3,-3
+pub fn get_fixed_integers<T, const N: usize>(s: &str) -> AocResult<[T; N]>
+where
+    T: FromStr<Err = ParseIntError> + Debug,
+{
+    let integers = get_integers::<T>(s)?;
+    let number_of_integers = integers.len();
+
+    integers
+        .try_into()
+        .map_err(|_| AocError::parse(format!("Expected {} numbers, found {}", N, number_of_integers)))
+}
+
+fn integer_spans(s: &str) -> Vec<(usize, usize)> {
+    let bytes = s.as_bytes();
+    let mut spans = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if !bytes[index].is_ascii_digit() {
+            index += 1;
+            continue;
+        }
+
+        let has_sign = index > 0
+            && bytes[index - 1] == b'-'
+            && (index < 2 || !bytes[index - 2].is_ascii_alphanumeric());
+        let start = if has_sign { index - 1 } else { index };
+
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+
+        spans.push((start, index));
+    }
+
+    spans
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub fn separated_from_by(&self, other: &Position) -> Separation {
+        let dx = self.x as i32 - other.x as i32;
+        let dy = self.y as i32 - other.y as i32;
+
+        Separation { dx, dy }
+    }
+
+    pub fn walk_in_direction(&self, direction: &Direction) -> Option<Position> {
+        let movement = direction.get_movement();
+
+        self.new_position(&movement)
+    }
+
+    pub fn new_position(&self, separation: &Separation) -> Option<Position> {
+        let x = self.x as i32 + separation.dx;
+        let y = self.y as i32 + separation.dy;
+        
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        Some(Position { x: x as usize, y: y as usize, })
+    }
+
+    pub fn is_adjacent_to(&self, other: &Position) -> bool {
+        let distance = self.x.abs_diff(other.x) + self.y.abs_diff(other.y);
+
+        distance == 1
+    }
+
+    // Same as stepping on a Torus of the given dimensions, an empty map leaves the position as it is
+    pub fn move_wrapping_around(
+        self, 
+        separation: &Separation, 
+        dimensions: &Dimensions
+    ) -> Self {
+        match torus::Torus::new(dimensions.width, dimensions.height) {
+            Some(torus) => torus.step(&self, &Vector::new(separation.dx as i64, separation.dy as i64)),
+            None => self,
+        }
+    }
+}
+
+pub fn greatest_common_divisor(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => greatest_common_divisor(b, a % b),
+    }
+}
+
+pub fn least_common_multiple(a: usize, b: usize) -> usize {
+    match greatest_common_divisor(a, b) {
+        0 => 0,
+        divisor => a / divisor * b,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Separation {
+    pub dx: i32,
+    pub dy: i32,
+}
+
+impl Separation {
+    pub fn negative(&self) -> Self {
+        Separation { dx: -self.dx, dy: -self.dy }
+    }
+
+    pub fn multiply(&self, n: usize) -> Self {
+        Separation { dx: (n as i32) * self.dx, dy: (n as i32) * self.dy }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+// Clockwise from north, each step is a 45 degree turn
+const CLOCKWISE_DIRECTIONS: [Direction; 8] = [
+    Direction::N,
+    Direction::NE,
+    Direction::E,
+    Direction::SE,
+    Direction::S,
+    Direction::SW,
+    Direction::W,
+    Direction::NW,
+];
+
+impl Direction {
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::N),
+            '>' => Some(Direction::E),
+            'v' => Some(Direction::S),
+            '<' => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    // U/D/L/R and N/E/S/W letters, kept apart from from_char since letters often mean something else on a map
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' => Some(Direction::N),
+            'R' | 'E' => Some(Direction::E),
+            'D' | 'S' => Some(Direction::S),
+            'L' | 'W' => Some(Direction::W),
+            _ => None,
+        }
+    }
+
+    pub fn cardinal() -> impl Iterator<Item = Direction> {
+        CLOCKWISE_DIRECTIONS.into_iter().step_by(2)
+    }
+
+    pub fn diagonal() -> impl Iterator<Item = Direction> {
+        CLOCKWISE_DIRECTIONS.into_iter().skip(1).step_by(2)
+    }
+
+    pub fn all() -> impl Iterator<Item = Direction> {
+        CLOCKWISE_DIRECTIONS.into_iter()
+    }
+
+    pub fn is_cardinal(&self) -> bool {
+        self.eighth_turns_from_north().is_multiple_of(2)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        !self.is_cardinal()
+    }
+
+    pub fn get_movement(&self) -> Separation {
+        match self {
+            Direction::N => Separation { dx: 0, dy: -1 },
+            Direction::NE => Separation { dx: 1, dy: -1 },
+            Direction::E => Separation { dx: 1, dy: 0 },
+            Direction::SE => Separation { dx: 1, dy: 1 },
+            Direction::S => Separation { dx: 0, dy: 1 },
+            Direction::SW => Separation { dx: -1, dy: 1 },
+            Direction::W => Separation { dx: -1, dy: 0 },
+            Direction::NW => Separation { dx: -1, dy: -1 },
+        }
+    }
+
+    // Returns None unless the angle is a multiple of 45 degrees
+    pub fn rotate_cw(&self, degrees: u32) -> Option<Self> {
+        match degrees % 45 {
+            0 => Some(self.rotate_eighth_turns((degrees / 45) as usize)),
+            _ => None,
+        }
+    }
+
+    pub fn rotate_ccw(&self, degrees: u32) -> Option<Self> {
+        match degrees % 45 {
+            0 => Some(self.rotate_eighth_turns(8 - (degrees / 45) as usize % 8)),
+            _ => None,
+        }
+    }
+
+    pub fn turn_left(&self) -> Self {
+        self.rotate_eighth_turns(6)
+    }
+
+    pub fn turn_right(&self) -> Self {
+        self.rotate_eighth_turns(2)
+    }
+
+    pub fn reverse(&self) -> Self {
+        self.rotate_eighth_turns(4)
+    }
+
+    pub fn opposite_to(&self, other: Direction) -> bool {
+        let opposite_direction = self.reverse();
+
+        opposite_direction == other
+    }
+
+    // Clockwise angle in degrees, in the range 0..360
+    pub fn clockwise_angle_to(&self, other: &Direction) -> u32 {
+        let eighth_turns = (8 + other.eighth_turns_from_north() - self.eighth_turns_from_north()) % 8;
+
+        45 * eighth_turns as u32
+    }
+
+    // Smallest angle in degrees between the two directions, in the range 0..=180
+    pub fn angle_between(&self, other: &Direction) -> u32 {
+        let clockwise_angle = self.clockwise_angle_to(other);
+
+        clockwise_angle.min(360 - clockwise_angle)
+    }
+
+    pub fn get_total_movement(&self, order: usize) -> Separation {
+        let single_separation = self.get_movement();
+
+        Separation{ 
+            dx: order as i32 * single_separation.dx, 
+            dy: order as i32 * single_separation.dy, 
+        }
+    }
+
+    fn eighth_turns_from_north(&self) -> usize {
+        match self {
+            Direction::N => 0,
+            Direction::NE => 1,
+            Direction::E => 2,
+            Direction::SE => 3,
+            Direction::S => 4,
+            Direction::SW => 5,
+            Direction::W => 6,
+            Direction::NW => 7,
+        }
+    }
+
+    fn rotate_eighth_turns(&self, eighth_turns: usize) -> Self {
+        CLOCKWISE_DIRECTIONS[(self.eighth_turns_from_north() + eighth_turns) % 8]
+    }
+}
+
+pub fn cardinal_directions() -> Vec<Direction> {
+    Direction::cardinal().collect()
+}
+
+pub fn diagonal_directions() -> Vec<Direction> {
+    Direction::diagonal().collect()
+}
+
+pub fn ordinal_directions() -> Vec<Direction> {
+    Direction::all().collect()
+}
+
+// Sparse counterpart of Grid::from_text_lines, lines don't need to have the same length
+pub fn position_map_from_text_lines<T> (
+    lines: &[String], 
+    parse_from_char: fn(char) -> Option<T>
+) -> AocResult<HashMap<Position, T>> {
+    let mut position_map = HashMap::new();
+
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let Some(value) = parse_from_char(c) else {
+                return Err(AocError::parse(format!("Unexpected character '{}'", c)).at_column(x).at_line(y, line));
+            };
+
+            position_map.insert(Position { x, y }, value);
+        }
+    }
+
+    Ok(position_map)
+}
+
+pub fn position_and_object_from_text_lines<T> (
+    lines: &[String], 
+    parse_from_char: fn(char) -> Option<T>
+) -> Option<(Position, T)> {
+    lines
+        .iter()
+        .enumerate()
+        .flat_map(|(y, line)|
+            line
+                .chars()
+                .enumerate()
+                .filter_map(|(x, c)| {
+                    let o = parse_from_char(c)?;
+                    let position = Position { x, y };
+
+                    Some((position, o))
+                    }
+                )
+                .collect::<Vec<(Position, T)>>()
+        )
+        .next()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Dimensions {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Dimensions {
+    pub fn includes(&self, position: &Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+}
+
+pub fn get_position_map_dimensions<T>(
+    position_map: &HashMap<Position, T>
+) -> Option<Dimensions> {
+    let width = 1 + position_map
+        .keys()
+        .map(|p| p.x)
+        .max()?;
+    let height = 1 + position_map
+        .keys()
+        .map(|p| p.y)
+        .max()?;
+    
+    Some( Dimensions { width, height, } )
+}
+
+pub fn positions_on_map_with_value<T>(
+    position_map: &HashMap<Position, T>,
+    value: T,
+) -> Vec<Position> where T: PartialEq {
+    position_map
+        .iter()
+        .filter(|(_p, v)| **v == value)
+        .map(|(p, _)| *p)
+        .collect::<Vec<Position>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case('^', Some(Direction::N))]
+    #[test_case('>', Some(Direction::E))]
+    #[test_case('v', Some(Direction::S))]
+    #[test_case('<', Some(Direction::W))]
+    #[test_case('U', None)]
+    #[test_case('S', None)]
+    #[test_case('#', None)]
+    fn test_direction_from_char(c: char, expected_direction: Option<Direction>) {
+        assert_eq!(Direction::from_char(c), expected_direction);
+    }
+
+    #[test_case('U', Some(Direction::N))]
+    #[test_case('R', Some(Direction::E))]
+    #[test_case('S', Some(Direction::S))]
+    #[test_case('L', Some(Direction::W))]
+    #[test_case('^', None)]
+    #[test_case('x', None)]
+    fn test_direction_from_letter(c: char, expected_direction: Option<Direction>) {
+        assert_eq!(Direction::from_letter(c), expected_direction);
+    }
+
+    #[test_case(Direction::N, 45, Some(Direction::NE))]
+    #[test_case(Direction::NW, 90, Some(Direction::NE))]
+    #[test_case(Direction::SE, 405, Some(Direction::S))]
+    #[test_case(Direction::E, 0, Some(Direction::E))]
+    #[test_case(Direction::E, 30, None)]
+    fn test_rotate_cw(direction: Direction, degrees: u32, expected_direction: Option<Direction>) {
+        assert_eq!(direction.rotate_cw(degrees), expected_direction);
+    }
+
+    #[test_case(Direction::N, 45, Some(Direction::NW))]
+    #[test_case(Direction::NE, 90, Some(Direction::NW))]
+    #[test_case(Direction::S, 360, Some(Direction::S))]
+    #[test_case(Direction::W, 100, None)]
+    fn test_rotate_ccw(direction: Direction, degrees: u32, expected_direction: Option<Direction>) {
+        assert_eq!(direction.rotate_ccw(degrees), expected_direction);
+    }
+
+    #[test]
+    fn test_turns_do_not_panic_on_diagonals() {
+        for direction in Direction::all() {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert!(direction.opposite_to(direction.reverse()));
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+        }
+
+        assert_eq!(Direction::NE.reverse(), Direction::SW);
+        assert_eq!(Direction::SE.turn_left(), Direction::NE);
+    }
+
+    #[test_case(Direction::N, Direction::E, 90, 90)]
+    #[test_case(Direction::E, Direction::N, 270, 90)]
+    #[test_case(Direction::NE, Direction::SW, 180, 180)]
+    #[test_case(Direction::W, Direction::NE, 135, 135)]
+    #[test_case(Direction::S, Direction::S, 0, 0)]
+    fn test_angles(first: Direction, second: Direction, expected_clockwise_angle: u32, expected_angle: u32) {
+        assert_eq!(first.clockwise_angle_to(&second), expected_clockwise_angle);
+        assert_eq!(first.angle_between(&second), expected_angle);
+    }
+
+    #[test_case("3   4", vec![3, 4])]
+    #[test_case("p=0,4 v=3,-3", vec![0, 4, 3, -3])]
+    #[test_case("-7 at the start", vec![-7])]
+    #[test_case("ranges 1-3", vec![1, 3])]
+    #[test_case("no numbers", vec![])]
+    fn test_get_integers(s: &str, expected_integers: Vec<i64>) {
+        assert_eq!(get_integers::<i64>(s).unwrap(), expected_integers);
+    }
+
+    #[test]
+    fn test_get_integers_rejects_overflow_and_negative_unsigned() {
+        let Err(AocError::Parse(parse_error)) = get_integers::<i32>("1 99999999999") else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!(parse_error.column, Some(3));
+        assert!(get_integers::<u64>("x=-5").is_err());
+        assert_eq!(get_integers::<i128>("99999999999999999999").unwrap(), vec![99999999999999999999]);
+        assert_eq!(get_integers::<usize>("125 17").unwrap(), vec![125, 17]);
+    }
+
+    #[test]
+    fn test_get_fixed_integers() {
+        assert_eq!(get_fixed_integers::<i32, 4>("p=10,3 v=-1,2").unwrap(), [10, 3, -1, 2]);
+        assert!(get_fixed_integers::<i32, 4>("p=10,3 v=-1").is_err());
+    }
+
+    #[test_case(Position { x: 2, y: 4 }, Separation { dx: 2, dy: -3 }, Position { x: 4, y: 1 })]
+    #[test_case(Position { x: 10, y: 0 }, Separation { dx: 3, dy: -1 }, Position { x: 2, y: 6 })]
+    fn test_move_wrapping_around(position: Position, separation: Separation, expected_position: Position) {
+        let dimensions = Dimensions { width: 11, height: 7 };
+
+        assert_eq!(position.move_wrapping_around(&separation, &dimensions), expected_position);
+    }
+
+    #[test_case(12, 18, 6, 36)]
+    #[test_case(101, 103, 1, 10403)]
+    #[test_case(0, 5, 5, 0)]
+    fn test_divisors_and_multiples(a: usize, b: usize, expected_divisor: usize, expected_multiple: usize) {
+        assert_eq!(greatest_common_divisor(a, b), expected_divisor);
+        assert_eq!(least_common_multiple(a, b), expected_multiple);
+    }
+
+    #[test]
+    fn test_direction_iterators() {
+        assert_eq!(
+            Direction::cardinal().collect::<Vec<Direction>>(),
+            vec![Direction::N, Direction::E, Direction::S, Direction::W],
+        );
+        assert_eq!(
+            Direction::diagonal().collect::<Vec<Direction>>(),
+            vec![Direction::NE, Direction::SE, Direction::SW, Direction::NW],
+        );
+        assert_eq!(Direction::all().count(), 8);
+        assert!(Direction::cardinal().all(|d| d.is_cardinal()));
+        assert!(Direction::diagonal().all(|d| d.is_diagonal()));
+    }
+}
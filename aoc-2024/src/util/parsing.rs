@@ -0,0 +1,257 @@
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::error::{AocError, AocResult};
+
+// A regex that is compiled the first time it is used, so it can be kept in a static
+pub struct LazyRegex {
+    pattern: &'static str,
+    regex: OnceLock<Regex>,
+}
+
+impl LazyRegex {
+    pub const fn new(pattern: &'static str) -> Self {
+        LazyRegex { pattern, regex: OnceLock::new() }
+    }
+
+    pub fn pattern(&self) -> &'static str {
+        self.pattern
+    }
+}
+
+impl Deref for LazyRegex {
+    type Target = Regex;
+
+    fn deref(&self) -> &Regex {
+        self.regex.get_or_init(|| Regex::new(self.pattern).expect("Static regex patterns are valid"))
+    }
+}
+
+// A template for a whole line where every {} is a field, e.g. "Button {}: X+{}, Y+{}".
+// Everything else has to match literally, whitespace around the line is ignored.
+pub struct LinePattern {
+    template: &'static str,
+    description: Option<&'static str>,
+    regex: OnceLock<Regex>,
+}
+
+impl LinePattern {
+    pub const fn new(template: &'static str) -> Self {
+        LinePattern { template, description: None, regex: OnceLock::new() }
+    }
+
+    // Used in the error message when a line doesn't match, e.g. "a robot like p=0,4 v=3,-3"
+    pub const fn described_as(template: &'static str, description: &'static str) -> Self {
+        LinePattern { template, description: Some(description), regex: OnceLock::new() }
+    }
+
+    pub fn template(&self) -> &'static str {
+        self.template
+    }
+
+    pub fn number_of_fields(&self) -> usize {
+        self.template.matches("{}").count()
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        self.regex().is_match(line)
+    }
+
+    // The text of every field along with its 0-based column in the line
+    pub fn fields<'a>(&self, line: &'a str) -> AocResult<Vec<(usize, &'a str)>> {
+        let captures = self.regex().captures(line).ok_or_else(|| {
+            let message = match self.description {
+                Some(description) => format!("Expected {}", description),
+                None => format!("Expected a line like {}", self.template),
+            };
+
+            AocError::parse(message)
+        })?;
+
+        Ok(captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .map(|m| (m.start(), m.as_str()))
+            .collect())
+    }
+
+    pub fn parse<T: FromFields>(&self, line: &str) -> AocResult<T> {
+        let fields = self.fields(line)?;
+
+        if fields.len() != T::NUMBER_OF_FIELDS {
+            return Err(AocError::parse(format!(
+                "Pattern {} has {} fields, but {} are expected",
+                self.template,
+                fields.len(),
+                T::NUMBER_OF_FIELDS,
+            )));
+        }
+
+        T::from_fields(&fields)
+    }
+
+    fn regex(&self) -> &Regex {
+        self.regex.get_or_init(|| {
+            let literals = self.template.split("{}").map(regex::escape).collect::<Vec<String>>();
+
+            Regex::new(&format!(r"^\s*{}\s*$", literals.join("(.+?)"))).expect("Escaped templates are valid regexes")
+        })
+    }
+}
+
+// Anything that can be built from the fields of a line pattern, in the order they appear
+pub trait FromFields: Sized {
+    const NUMBER_OF_FIELDS: usize;
+
+    fn from_fields(fields: &[(usize, &str)]) -> AocResult<Self>;
+}
+
+pub fn parse_field<T>((column, text): (usize, &str)) -> AocResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text
+        .parse::<T>()
+        .map_err(|e| AocError::parse(format!("Invalid field {}: {}", text, e)).at_column(column))
+}
+
+macro_rules! impl_from_fields_for_tuple {
+    ($count:expr; $($field_type:ident),+) => {
+        impl<$($field_type),+> FromFields for ($($field_type,)+)
+        where
+            $($field_type: FromStr, $field_type::Err: Display),+
+        {
+            const NUMBER_OF_FIELDS: usize = $count;
+
+            fn from_fields(fields: &[(usize, &str)]) -> AocResult<Self> {
+                let mut fields = fields.iter();
+
+                Ok(($(parse_field::<$field_type>(*fields.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+impl_from_fields_for_tuple!(1; A);
+impl_from_fields_for_tuple!(2; A, B);
+impl_from_fields_for_tuple!(3; A, B, C);
+impl_from_fields_for_tuple!(4; A, B, C, D);
+impl_from_fields_for_tuple!(5; A, B, C, D, E);
+impl_from_fields_for_tuple!(6; A, B, C, D, E, F);
+
+impl<T, const N: usize> FromFields for [T; N]
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    const NUMBER_OF_FIELDS: usize = N;
+
+    fn from_fields(fields: &[(usize, &str)]) -> AocResult<Self> {
+        let values = fields.iter().map(|f| parse_field::<T>(*f)).collect::<AocResult<Vec<T>>>()?;
+
+        values
+            .try_into()
+            .map_err(|_| AocError::parse(format!("Expected {} fields", N)))
+    }
+}
+
+// A field holding any number of values separated by whitespace or commas, e.g. "10 19" or "75,47,61"
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct List<T>(pub Vec<T>);
+
+impl<T: FromStr> FromStr for List<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<T>())
+            .collect::<Result<Vec<T>, T::Err>>()
+            .map(List)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use test_case::test_case;
+
+    static BUTTON: LinePattern = LinePattern::new("Button {}: X+{}, Y+{}");
+
+    #[test]
+    fn test_lazy_regex() {
+        static DIGITS: LazyRegex = LazyRegex::new(r"\d+");
+
+        assert_eq!(DIGITS.find_iter("a1b22c333").count(), 3);
+        assert_eq!(DIGITS.pattern(), r"\d+");
+    }
+
+    #[test]
+    fn test_parse_tuple() {
+        assert_eq!(BUTTON.parse::<(char, i64, i64)>("Button A: X+94, Y+34").unwrap(), ('A', 94, 34));
+        assert_eq!(BUTTON.number_of_fields(), 3);
+    }
+
+    #[test]
+    fn test_parse_array() {
+        let pattern = LinePattern::new("p={},{} v={},{}");
+
+        assert_eq!(pattern.parse::<[i64; 4]>("  p=0,4 v=3,-3 ").unwrap(), [0, 4, 3, -3]);
+    }
+
+    #[test]
+    fn test_template_is_matched_literally() {
+        let pattern = LinePattern::new("{}|{}");
+
+        assert!(pattern.matches("47|53"));
+        assert!(!pattern.matches("4753"));
+        assert!(!BUTTON.matches("Button A: X=94, Y=34"));
+        assert!(!BUTTON.matches("Button A: X+94 Y+34"));
+        assert!(BUTTON.parse::<(char, i64, i64)>("Button A: X+94, Y+34 extra").is_err());
+    }
+
+    #[test_case("Button A: X=94, Y=34", "Expected a line like Button {}: X+{}, Y+{}", None)]
+    #[test_case("Button A: X+9z, Y+34", "Invalid field 9z: invalid digit found in string", Some(13))]
+    fn test_parse_errors(line: &str, expected_message: &str, expected_column: Option<usize>) {
+        let result = BUTTON.parse::<(char, i64, i64)>(line);
+
+        match result {
+            Err(AocError::Parse(ParseError { message, column, .. })) => {
+                assert_eq!(message, expected_message);
+                assert_eq!(column, expected_column);
+            },
+            _ => panic!("Expected a parse error"),
+        }
+    }
+
+    #[test]
+    fn test_description_is_used_in_error() {
+        let pattern = LinePattern::described_as("{}: {}", "a calibration line like 190: 10 19");
+
+        assert!(matches!(
+            pattern.parse::<(usize, List<usize>)>("190 10 19"),
+            Err(AocError::Parse(ParseError { message, .. })) if message == "Expected a calibration line like 190: 10 19"
+        ));
+    }
+
+    #[test]
+    fn test_wrong_number_of_fields() {
+        assert!(BUTTON.parse::<(char, i64)>("Button A: X+94, Y+34").is_err());
+    }
+
+    #[test_case("190: 10 19", (190, vec![10, 19]))]
+    #[test_case("7290: 6,8,6,15", (7290, vec![6, 8, 6, 15]))]
+    fn test_parse_list(line: &str, (expected_result, expected_inputs): (usize, Vec<usize>)) {
+        let pattern = LinePattern::new("{}: {}");
+
+        assert_eq!(pattern.parse::<(usize, List<usize>)>(line).unwrap(), (expected_result, List(expected_inputs)));
+    }
+}
@@ -0,0 +1,260 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::util::{Position, Separation};
+
+// Integer types that can be used as coordinates
+pub trait Coordinate: Copy + Ord + Hash + Debug + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    fn abs_difference(self, other: Self) -> Self;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn abs_difference(self, other: Self) -> Self {
+                    match self > other {
+                        true => self - other,
+                        false => other - self,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i32, i64, isize, u32, u64, usize);
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Vector<T> {
+    pub dx: T,
+    pub dy: T,
+}
+
+impl<T: Coordinate> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+
+    pub fn origin() -> Self {
+        Point { x: T::ZERO, y: T::ZERO }
+    }
+
+    pub fn checked_add(&self, vector: &Vector<T>) -> Option<Self> {
+        Some(Point { x: self.x.checked_add(vector.dx)?, y: self.y.checked_add(vector.dy)? })
+    }
+
+    pub fn checked_sub(&self, vector: &Vector<T>) -> Option<Self> {
+        Some(Point { x: self.x.checked_sub(vector.dx)?, y: self.y.checked_sub(vector.dy)? })
+    }
+
+    // The vector that takes `other` to this point
+    pub fn checked_vector_from(&self, other: &Point<T>) -> Option<Vector<T>> {
+        Some(Vector { dx: self.x.checked_sub(other.x)?, dy: self.y.checked_sub(other.y)? })
+    }
+
+    pub fn manhattan_distance(&self, other: &Point<T>) -> T {
+        self.x.abs_difference(other.x) + self.y.abs_difference(other.y)
+    }
+
+    pub fn chebyshev_distance(&self, other: &Point<T>) -> T {
+        self.x.abs_difference(other.x).max(self.y.abs_difference(other.y))
+    }
+
+    pub fn try_convert<U>(&self) -> Option<Point<U>> where U: TryFrom<T> {
+        Some(Point { x: U::try_from(self.x).ok()?, y: U::try_from(self.y).ok()? })
+    }
+
+    pub fn to_position(&self) -> Option<Position> where usize: TryFrom<T> {
+        let point = self.try_convert::<usize>()?;
+
+        Some(Position { x: point.x, y: point.y })
+    }
+}
+
+impl<T: Coordinate> Vector<T> {
+    pub fn new(dx: T, dy: T) -> Self {
+        Vector { dx, dy }
+    }
+
+    pub fn checked_add(&self, other: &Vector<T>) -> Option<Self> {
+        Some(Vector { dx: self.dx.checked_add(other.dx)?, dy: self.dy.checked_add(other.dy)? })
+    }
+
+    pub fn checked_mul(&self, factor: T) -> Option<Self> {
+        Some(Vector { dx: self.dx.checked_mul(factor)?, dy: self.dy.checked_mul(factor)? })
+    }
+
+    pub fn manhattan_length(&self) -> T {
+        self.dx.abs_difference(T::ZERO) + self.dy.abs_difference(T::ZERO)
+    }
+
+    pub fn chebyshev_length(&self) -> T {
+        self.dx.abs_difference(T::ZERO).max(self.dy.abs_difference(T::ZERO))
+    }
+
+    pub fn try_convert<U>(&self) -> Option<Vector<U>> where U: TryFrom<T> {
+        Some(Vector { dx: U::try_from(self.dx).ok()?, dy: U::try_from(self.dy).ok()? })
+    }
+}
+
+impl<T: Coordinate> Add<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, vector: Vector<T>) -> Point<T> {
+        Point { x: self.x + vector.dx, y: self.y + vector.dy }
+    }
+}
+
+impl<T: Coordinate> Sub<Vector<T>> for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, vector: Vector<T>) -> Point<T> {
+        Point { x: self.x - vector.dx, y: self.y - vector.dy }
+    }
+}
+
+impl<T: Coordinate> Sub<Point<T>> for Point<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Point<T>) -> Vector<T> {
+        Vector { dx: self.x - other.x, dy: self.y - other.y }
+    }
+}
+
+impl<T: Coordinate> Add<Vector<T>> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn add(self, other: Vector<T>) -> Vector<T> {
+        Vector { dx: self.dx + other.dx, dy: self.dy + other.dy }
+    }
+}
+
+impl<T: Coordinate> Sub<Vector<T>> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn sub(self, other: Vector<T>) -> Vector<T> {
+        Vector { dx: self.dx - other.dx, dy: self.dy - other.dy }
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Vector<T> {
+    type Output = Vector<T>;
+
+    fn mul(self, factor: T) -> Vector<T> {
+        Vector { dx: self.dx * factor, dy: self.dy * factor }
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Vector<T> {
+    type Output = Vector<T>;
+
+    fn neg(self) -> Vector<T> {
+        Vector { dx: -self.dx, dy: -self.dy }
+    }
+}
+
+impl From<Position> for Point<usize> {
+    fn from(position: Position) -> Self {
+        Point { x: position.x, y: position.y }
+    }
+}
+
+impl From<Separation> for Vector<i32> {
+    fn from(separation: Separation) -> Self {
+        Vector { dx: separation.dx, dy: separation.dy }
+    }
+}
+
+impl Position {
+    pub fn to_point<T>(&self) -> Option<Point<T>> where T: Coordinate + TryFrom<usize> {
+        Point::from(*self).try_convert::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_operators() {
+        let point = Point::new(3, -2);
+        let vector = Vector::new(-5, 4);
+
+        assert_eq!(point + vector, Point::new(-2, 2));
+        assert_eq!(point - vector, Point::new(8, -6));
+        assert_eq!(Point::new(-2, 2) - point, vector);
+        assert_eq!(vector * 3, Vector::new(-15, 12));
+        assert_eq!(-vector, Vector::new(5, -4));
+        assert_eq!(vector + vector - vector, vector);
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let origin = Point::<usize>::origin();
+
+        assert_eq!(origin.checked_sub(&Vector::new(1, 0)), None);
+        assert_eq!(origin.checked_add(&Vector::new(1, 2)), Some(Point::new(1, 2)));
+        assert_eq!(Point::new(i64::MAX, 0).checked_add(&Vector::new(1, 0)), None);
+        assert_eq!(Vector::new(i32::MAX, 1).checked_mul(2), None);
+        assert_eq!(Point::new(1usize, 1).checked_vector_from(&Point::new(2, 0)), None);
+        assert_eq!(Point::new(1i32, 1).checked_vector_from(&Point::new(2, 0)), Some(Vector::new(-1, 1)));
+    }
+
+    #[test_case(Point::new(0, 0), Point::new(3, -4), 7, 4)]
+    #[test_case(Point::new(-1, -1), Point::new(-1, -1), 0, 0)]
+    #[test_case(Point::new(5, 2), Point::new(-5, 3), 11, 10)]
+    fn test_distances(first: Point<i64>, second: Point<i64>, expected_manhattan: i64, expected_chebyshev: i64) {
+        assert_eq!(first.manhattan_distance(&second), expected_manhattan);
+        assert_eq!(first.chebyshev_distance(&second), expected_chebyshev);
+        assert_eq!((second - first).manhattan_length(), expected_manhattan);
+        assert_eq!((second - first).chebyshev_length(), expected_chebyshev);
+    }
+
+    #[test]
+    fn test_unsigned_distances() {
+        assert_eq!(Point::new(2usize, 7).manhattan_distance(&Point::new(5, 1)), 9);
+    }
+
+    #[test]
+    fn test_position_conversions() {
+        let position = Position { x: 4, y: 9 };
+
+        assert_eq!(position.to_point::<i32>(), Some(Point::new(4, 9)));
+        assert_eq!(Point::new(4i64, 9).to_position(), Some(position));
+        assert_eq!(Point::new(-1i64, 9).to_position(), None);
+        assert_eq!(Vector::from(Separation { dx: -1, dy: 2 }), Vector::new(-1, 2));
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use crate::util::{cardinal_directions, Direction, Grid, Position};
+
+// Disjoint sets over the indices 0..n, with path halving and union by size
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind { parents: (0..n).collect(), sizes: vec![1; n] }
+    }
+
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    // Returns false when the two were already in the same set
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (root_i, root_j) = (self.find(i), self.find(j));
+
+        if root_i == root_j {
+            return false;
+        }
+
+        let (larger, smaller) = match self.sizes[root_i] >= self.sizes[root_j] {
+            true => (root_i, root_j),
+            false => (root_j, root_i),
+        };
+
+        self.parents[smaller] = larger;
+        self.sizes[larger] += self.sizes[smaller];
+
+        true
+    }
+
+    pub fn set_size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+
+        self.sizes[root]
+    }
+}
+
+// Smallest rectangle holding a set of positions, both corners are included
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BoundingBox {
+    pub min: Position,
+    pub max: Position,
+}
+
+impl BoundingBox {
+    fn around(position: &Position) -> Self {
+        BoundingBox { min: *position, max: *position }
+    }
+
+    fn extend_to(&mut self, position: &Position) {
+        self.min = Position { x: self.min.x.min(position.x), y: self.min.y.min(position.y) };
+        self.max = Position { x: self.max.x.max(position.x), y: self.max.y.max(position.y) };
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.y - self.min.y + 1
+    }
+
+    pub fn includes(&self, position: &Position) -> bool {
+        (self.min.x..=self.max.x).contains(&position.x) && (self.min.y..=self.max.y).contains(&position.y)
+    }
+}
+
+#[derive(Debug)]
+pub struct Region {
+    label: usize,
+    cells: Vec<Position>,
+    bounding_box: BoundingBox,
+}
+
+impl Region {
+    fn from_cells(label: usize, cells: Vec<Position>) -> Self {
+        let mut bounding_box = BoundingBox::around(&cells[0]);
+
+        for cell in cells.iter() {
+            bounding_box.extend_to(cell);
+        }
+
+        Region { label, cells, bounding_box }
+    }
+
+    pub fn label(&self) -> usize {
+        self.label
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn cells(&self) -> &[Position] {
+        &self.cells
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.bounding_box
+    }
+}
+
+// Every cell of the grid belongs to exactly one region, labels follow the row-major order
+// of the first cell of each region
+pub struct RegionLabels {
+    labels: Grid<usize>,
+    regions: Vec<Region>,
+}
+
+impl RegionLabels {
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn label_of(&self, position: &Position) -> Option<usize> {
+        self.labels.get(position).copied()
+    }
+
+    pub fn region_at(&self, position: &Position) -> Option<&Region> {
+        self.label_of(position).map(|l| &self.regions[l])
+    }
+
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+}
+
+// Neighbouring cells (north, east, south, west) belong to the same region when `are_connected` holds
+pub fn label_regions<T>(grid: &Grid<T>, are_connected: impl Fn(&T, &T) -> bool) -> RegionLabels {
+    let width = grid.width();
+    let mut union_find = UnionFind::new(width * grid.height());
+
+    for (position, value) in grid.iter() {
+        let index = position.y * width + position.x;
+
+        for direction in [Direction::E, Direction::S] {
+            let Some(neighbour) = position.walk_in_direction(&direction) else { continue };
+
+            if grid.get(&neighbour).is_some_and(|v| are_connected(value, v)) {
+                union_find.union(index, neighbour.y * width + neighbour.x);
+            }
+        }
+    }
+
+    let mut label_of_root = HashMap::new();
+    let mut region_cells: Vec<Vec<Position>> = Vec::new();
+
+    let labels = grid.positions().map(|position| {
+        let root = union_find.find(position.y * width + position.x);
+        let label = *label_of_root.entry(root).or_insert_with(|| {
+            region_cells.push(Vec::new());
+            region_cells.len() - 1
+        });
+
+        region_cells[label].push(position);
+
+        label
+    }).collect::<Vec<usize>>();
+
+    let regions = region_cells
+        .into_iter()
+        .enumerate()
+        .map(|(label, cells)| Region::from_cells(label, cells))
+        .collect();
+
+    RegionLabels { labels: Grid::new(width, grid.height(), labels).unwrap(), regions }
+}
+
+// Cells reachable from the start through connected neighbours, the start included
+pub fn flood_fill<T>(grid: &Grid<T>, start: &Position, are_connected: impl Fn(&T, &T) -> bool) -> Vec<Position> {
+    if !grid.includes(start) {
+        return Vec::new();
+    }
+
+    let mut is_filled = Grid::filled(grid.width(), grid.height(), false);
+    let mut stack = vec![*start];
+    let mut filled = Vec::new();
+
+    is_filled[*start] = true;
+
+    while let Some(position) = stack.pop() {
+        filled.push(position);
+
+        for (_, neighbour, value) in grid.neighbours(&position, &cardinal_directions()) {
+            if !is_filled[neighbour] && are_connected(&grid[position], value) {
+                is_filled[neighbour] = true;
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::split_lines;
+
+    fn garden() -> Grid<char> {
+        Grid::from_text_lines(&split_lines("AAB\nABB\nCCB"), Some).unwrap()
+    }
+
+    #[test]
+    fn test_union_find() {
+        let mut union_find = UnionFind::new(5);
+
+        assert!(union_find.union(0, 1));
+        assert!(union_find.union(3, 4));
+        assert!(union_find.union(1, 4));
+        assert!(!union_find.union(0, 3));
+        assert_eq!(union_find.set_size(4), 4);
+        assert_ne!(union_find.find(2), union_find.find(0));
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let region_labels = label_regions(&garden(), |a, b| a == b);
+
+        let areas = region_labels.regions().iter().map(|r| r.area()).collect::<Vec<usize>>();
+        assert_eq!(areas, vec![3, 4, 2]);
+
+        assert_eq!(region_labels.label_of(&Position { x: 1, y: 2 }), Some(2));
+        assert_eq!(region_labels.region_at(&Position { x: 2, y: 2 }).unwrap().label(), 1);
+        assert_eq!(region_labels.label_of(&Position { x: 3, y: 0 }), None);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let region_labels = label_regions(&garden(), |a, b| a == b);
+        let bounding_box = region_labels.regions()[1].bounding_box();
+
+        assert_eq!(bounding_box, BoundingBox { min: Position { x: 1, y: 0 }, max: Position { x: 2, y: 2 } });
+        assert_eq!((bounding_box.width(), bounding_box.height()), (2, 3));
+        assert!(bounding_box.includes(&Position { x: 1, y: 2 }));
+        assert!(!bounding_box.includes(&Position { x: 0, y: 1 }));
+    }
+
+    #[test]
+    fn test_separate_regions_with_the_same_value() {
+        let grid = Grid::from_text_lines(&split_lines("ABA\nBBB\nABA"), Some).unwrap();
+
+        let region_labels = label_regions(&grid, |a, b| a == b);
+
+        assert_eq!(region_labels.regions().len(), 5);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut filled = flood_fill(&garden(), &Position { x: 0, y: 0 }, |a, b| a == b);
+        filled.sort_by_key(|p| (p.y, p.x));
+
+        assert_eq!(filled, vec![Position { x: 0, y: 0 }, Position { x: 1, y: 0 }, Position { x: 0, y: 1 }]);
+        assert!(flood_fill(&garden(), &Position { x: 5, y: 0 }, |a, b| a == b).is_empty());
+    }
+}
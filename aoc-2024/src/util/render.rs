@@ -0,0 +1,398 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::AocResult;
+use crate::input::InputError;
+use crate::util::lines::bresenham_line;
+use crate::util::{Direction, Grid, Point, Position};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const BLACK: Colour = Colour { r: 0, g: 0, b: 0 };
+    pub const WHITE: Colour = Colour { r: 255, g: 255, b: 255 };
+    pub const GREY: Colour = Colour { r: 128, g: 128, b: 128 };
+    pub const RED: Colour = Colour { r: 220, g: 50, b: 47 };
+    pub const GREEN: Colour = Colour { r: 133, g: 153, b: 0 };
+    pub const BLUE: Colour = Colour { r: 38, g: 139, b: 210 };
+    pub const YELLOW: Colour = Colour { r: 181, g: 137, b: 0 };
+
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Colour { r, g, b }
+    }
+
+    // Evenly spread hues, handy for telling regions apart
+    pub fn from_label(label: usize) -> Self {
+        let hue = (label as f64 * 0.618_034).fract() * 6.0;
+        let rising = (hue.fract() * 200.0) as u8 + 55;
+        let falling = 255 - rising + 55;
+
+        match hue as usize {
+            0 => Colour::new(255, rising, 55),
+            1 => Colour::new(falling, 255, 55),
+            2 => Colour::new(55, 255, rising),
+            3 => Colour::new(55, falling, 255),
+            4 => Colour::new(rising, 55, 255),
+            _ => Colour::new(255, 55, falling),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CellStyle {
+    pub symbol: char,
+    pub colour: Option<Colour>,
+    pub background: Option<Colour>,
+}
+
+impl CellStyle {
+    pub fn plain(symbol: char) -> Self {
+        CellStyle { symbol, colour: None, background: None }
+    }
+
+    pub fn coloured(symbol: char, colour: Colour) -> Self {
+        CellStyle { symbol, colour: Some(colour), background: None }
+    }
+
+    pub fn with_background(self, background: Colour) -> Self {
+        CellStyle { background: Some(background), ..self }
+    }
+
+    // In images each cell is a single colour, unstyled empty cells are black and the rest white
+    fn pixel_colour(&self) -> Colour {
+        self.background.or(self.colour).unwrap_or(match self.symbol {
+            ' ' | '.' => Colour::BLACK,
+            _ => Colour::WHITE,
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+// Styled cells that can be drawn over and then written out as text or as an image
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    cells: Grid<CellStyle>,
+}
+
+impl Canvas {
+    pub fn from_grid<T>(grid: &Grid<T>, style: impl Fn(&T) -> CellStyle) -> Self {
+        Canvas { cells: grid.map(style) }
+    }
+
+    // Sparse maps are drawn from (0, 0) up to the largest position, missing cells get the empty style
+    pub fn from_map<T>(map: &HashMap<Position, T>, empty: CellStyle, style: impl Fn(&T) -> CellStyle) -> Self {
+        let width = map.keys().map(|p| p.x + 1).max().unwrap_or(0);
+        let height = map.keys().map(|p| p.y + 1).max().unwrap_or(0);
+        let mut cells = Grid::filled(width, height, empty);
+
+        for (position, value) in map.iter() {
+            cells[*position] = style(value);
+        }
+
+        Canvas { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&CellStyle> {
+        self.cells.get(position)
+    }
+
+    pub fn set(&mut self, position: &Position, style: CellStyle) {
+        self.cells.set(position, style);
+    }
+
+    pub fn highlight(&mut self, positions: &[Position], background: Colour) {
+        for position in positions {
+            if let Some(cell) = self.cells.get_mut(position) {
+                cell.background = Some(background);
+            }
+        }
+    }
+
+    // Each step of the path is marked with an arrow pointing to the next position
+    pub fn draw_path(&mut self, path: &[Position], colour: Colour) {
+        for (from, to) in path.iter().zip(path.iter().skip(1)) {
+            let direction = Direction::cardinal().find(|d| from.walk_in_direction(d) == Some(*to));
+
+            let symbol = match direction {
+                Some(Direction::N) => '^',
+                Some(Direction::E) => '>',
+                Some(Direction::S) => 'v',
+                Some(Direction::W) => '<',
+                _ => '*',
+            };
+
+            if let Some(cell) = self.cells.get_mut(from) {
+                *cell = CellStyle { symbol, colour: Some(colour), ..*cell };
+            }
+        }
+    }
+
+    // Straight line between any two cells, parts outside the canvas are left out
+    pub fn draw_line(&mut self, from: &Position, to: &Position, style: CellStyle) {
+        let (from, to) = (Point::new(from.x as i64, from.y as i64), Point::new(to.x as i64, to.y as i64));
+
+        for position in bresenham_line(&from, &to).iter().filter_map(|p| p.to_position()) {
+            self.cells.set(&position, style);
+        }
+    }
+
+    pub fn to_ascii(&self) -> String {
+        self.cells
+            .rows()
+            .map(|row| row.iter().map(|c| c.symbol).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // 24-bit colour escape codes, every styled cell resets the colours after itself
+    pub fn to_ansi(&self) -> String {
+        self.cells
+            .rows()
+            .map(|row| row.iter().map(ansi_cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Each cell becomes a square of scale by scale pixels
+    pub fn to_pixels(&self, scale: usize) -> (usize, usize, Vec<Colour>) {
+        let scale = scale.max(1);
+        let (width, height) = (self.width() * scale, self.height() * scale);
+
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Position { x: x / scale, y: y / scale }))
+            .map(|p| self.cells[p].pixel_colour())
+            .collect();
+
+        (width, height, pixels)
+    }
+
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height, pixels) = self.to_pixels(scale);
+        let mut bytes = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+        bytes.extend(pixels.iter().flat_map(|c| [c.r, c.g, c.b]));
+        bytes
+    }
+
+    pub fn to_png(&self, scale: usize) -> Vec<u8> {
+        let (width, height, pixels) = self.to_pixels(scale);
+
+        encode_png(width, height, &pixels)
+    }
+
+    pub fn save_image(&self, path: &Path, format: ImageFormat, scale: usize) -> AocResult<()> {
+        let bytes = match format {
+            ImageFormat::Ppm => self.to_ppm(scale),
+            ImageFormat::Png => self.to_png(scale),
+        };
+
+        std::fs::write(path, bytes).map_err(|error| InputError::Unwritable { path: path.to_path_buf(), error }.into())
+    }
+}
+
+fn ansi_cell(cell: &CellStyle) -> String {
+    let mut text = String::new();
+
+    if let Some(c) = cell.colour {
+        text.push_str(&format!("\x1b[38;2;{};{};{}m", c.r, c.g, c.b));
+    }
+
+    if let Some(c) = cell.background {
+        text.push_str(&format!("\x1b[48;2;{};{};{}m", c.r, c.g, c.b));
+    }
+
+    text.push(cell.symbol);
+
+    if cell.colour.is_some() || cell.background.is_some() {
+        text.push_str("\x1b[0m");
+    }
+
+    text
+}
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+// Truecolour PNG without compression, the zlib stream only holds stored deflate blocks
+fn encode_png(width: usize, height: usize, pixels: &[Colour]) -> Vec<u8> {
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    header.extend([8, 2, 0, 0, 0]);
+
+    // Every scanline starts with filter type 0 (none)
+    let scanlines = pixels
+        .chunks(width.max(1))
+        .flat_map(|row| std::iter::once(0).chain(row.iter().flat_map(|c| [c.r, c.g, c.b])))
+        .collect::<Vec<u8>>();
+
+    let mut bytes = PNG_SIGNATURE.to_vec();
+    write_png_chunk(&mut bytes, b"IHDR", &header);
+    write_png_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+    write_png_chunk(&mut bytes, b"IEND", &[]);
+
+    bytes
+}
+
+fn write_png_chunk(bytes: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    let type_and_data = chunk_type.iter().chain(data.iter()).copied().collect::<Vec<u8>>();
+
+    bytes.extend((data.len() as u32).to_be_bytes());
+    bytes.extend(&type_and_data);
+    bytes.extend(crc32(&type_and_data).to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x78, 0x01];
+    let blocks = data.chunks(u16::MAX as usize).collect::<Vec<&[u8]>>();
+
+    if blocks.is_empty() {
+        bytes.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let is_final = i + 1 == blocks.len();
+        let length = block.len() as u16;
+
+        bytes.push(is_final as u8);
+        bytes.extend(length.to_le_bytes());
+        bytes.extend((!length).to_le_bytes());
+        bytes.extend(*block);
+    }
+
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::split_lines;
+
+    fn canvas() -> Canvas {
+        let grid = Grid::from_text_lines(&split_lines("#..\n.#."), Some).unwrap();
+
+        Canvas::from_grid(&grid, |c| CellStyle::plain(*c))
+    }
+
+    #[test]
+    fn test_to_ascii_with_path() {
+        let mut canvas = canvas();
+        canvas.draw_path(&[Position { x: 1, y: 0 }, Position { x: 2, y: 0 }, Position { x: 2, y: 1 }], Colour::RED);
+
+        assert_eq!(canvas.to_ascii(), "#>v\n.#.");
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let mut canvas = canvas();
+        canvas.draw_line(&Position { x: 0, y: 1 }, &Position { x: 4, y: 0 }, CellStyle::plain('*'));
+
+        assert_eq!(canvas.to_ascii(), "#.*\n**.");
+    }
+
+    #[test]
+    fn test_from_map() {
+        let map = HashMap::from([(Position { x: 2, y: 1 }, 7), (Position { x: 0, y: 0 }, 3)]);
+
+        let canvas = Canvas::from_map(&map, CellStyle::plain('.'), |n| CellStyle::plain(char::from_digit(*n, 10).unwrap()));
+
+        assert_eq!(canvas.to_ascii(), "3..\n..7");
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let mut canvas = canvas();
+        canvas.highlight(&[Position { x: 0, y: 1 }], Colour::BLUE);
+        canvas.set(&Position { x: 1, y: 1 }, CellStyle::coloured('@', Colour::RED));
+
+        assert_eq!(canvas.to_ansi(), "#..\n\x1b[48;2;38;139;210m.\x1b[0m\x1b[38;2;220;50;47m@\x1b[0m.");
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let ppm = canvas().to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        assert_eq!(&ppm[header.len()..header.len() + 9], &[255, 255, 255, 255, 255, 255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_to_png() {
+        let png = canvas().to_png(1);
+
+        assert_eq!(&png[..8], &PNG_SIGNATURE);
+        assert_eq!(&png[16..24], &[0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(&png[png.len() - 12..], &[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]);
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_image_format_from_path() {
+        assert_eq!(ImageFormat::from_path(Path::new("out/map.png")), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_path(Path::new("map.ppm")), Some(ImageFormat::Ppm));
+        assert_eq!(ImageFormat::from_path(Path::new("map.txt")), None);
+    }
+}
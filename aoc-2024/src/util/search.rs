@@ -0,0 +1,367 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// Outcome of a search, the goal fields are only set when a goal state was reached.
+// Without a goal the whole reachable state space is explored.
+#[derive(Debug)]
+pub struct SearchResult<S> {
+    cost: Option<usize>,
+    starts: HashSet<S>,
+    goals: Vec<S>,
+    costs: HashMap<S, usize>,
+    predecessors: HashMap<S, Vec<S>>,
+    visited: HashSet<S>,
+}
+
+impl<S: Clone + Eq + Hash> SearchResult<S> {
+    fn new() -> Self {
+        SearchResult {
+            cost: None,
+            starts: HashSet::new(),
+            goals: Vec::new(),
+            costs: HashMap::new(),
+            predecessors: HashMap::new(),
+            visited: HashSet::new(),
+        }
+    }
+
+    pub fn cost(&self) -> Option<usize> {
+        self.cost
+    }
+
+    // Every goal state reached at the optimal cost
+    pub fn goals(&self) -> &[S] {
+        &self.goals
+    }
+
+    pub fn cost_to(&self, state: &S) -> Option<usize> {
+        match self.visited.contains(state) {
+            true => self.costs.get(state).copied(),
+            false => None,
+        }
+    }
+
+    // All states that lead to the given state at its optimal cost
+    pub fn predecessors(&self, state: &S) -> &[S] {
+        self.predecessors.get(state).map(|p| p.as_slice()).unwrap_or(&[])
+    }
+
+    pub fn visited(&self) -> impl Iterator<Item = &S> {
+        self.visited.iter()
+    }
+
+    pub fn has_visited(&self, state: &S) -> bool {
+        self.visited.contains(state)
+    }
+
+    // One optimal path from a start state to the first goal, both ends included
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.path_to(self.goals.first()?)
+    }
+
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.visited.contains(state) {
+            return None;
+        }
+
+        let mut path = vec![state.clone()];
+
+        // Zero-cost steps can give a start state predecessors, following them would go round in circles
+        while !self.starts.contains(path.last()?) {
+            match self.predecessors(path.last()?).first() {
+                Some(predecessor) => path.push(predecessor.clone()),
+                None => break,
+            }
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+
+    // Every state that lies on at least one optimal path to a goal
+    pub fn states_on_optimal_paths(&self) -> HashSet<S> {
+        let mut states = HashSet::new();
+        let mut stack = self.goals.clone();
+
+        while let Some(state) = stack.pop() {
+            if states.insert(state.clone()) {
+                stack.extend(self.predecessors(&state).iter().cloned());
+            }
+        }
+
+        states
+    }
+
+    pub fn optimal_path_count(&self, state: &S) -> usize {
+        let mut path_counts = HashMap::new();
+
+        self.count_optimal_paths(state, &mut path_counts)
+    }
+
+    fn count_optimal_paths(&self, state: &S, path_counts: &mut HashMap<S, usize>) -> usize {
+        if let Some(path_count) = path_counts.get(state) {
+            return *path_count;
+        }
+
+        let path_count = match (self.visited.contains(state), self.predecessors(state)) {
+            (false, _) => 0,
+            (true, []) => 1,
+            (true, predecessors) => predecessors.iter().map(|p| self.count_optimal_paths(p, path_counts)).sum(),
+        };
+
+        path_counts.insert(state.clone(), path_count);
+
+        path_count
+    }
+
+    fn reach_goal(&mut self, state: S, cost: usize) {
+        self.cost.get_or_insert(cost);
+        self.goals.push(state);
+    }
+}
+
+// Every step costs one, the neighbours of a state are the states one step away
+pub fn breadth_first_search<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if result.costs.insert(start.clone(), 0).is_none() {
+            result.starts.insert(start.clone());
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        let cost = result.costs[&state];
+
+        // Finish the level of the goal so that all of its optimal predecessors are known
+        if result.cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+
+        result.visited.insert(state.clone());
+
+        if is_goal(&state) {
+            result.reach_goal(state, cost);
+            continue;
+        }
+
+        for next_state in neighbours(&state) {
+            match result.costs.get(&next_state) {
+                Some(&next_cost) if next_cost == cost + 1 => {
+                    result.predecessors.entry(next_state).or_default().push(state.clone());
+                },
+                Some(_) => {},
+                None => {
+                    result.costs.insert(next_state.clone(), cost + 1);
+                    result.predecessors.insert(next_state.clone(), vec![state.clone()]);
+                    queue.push_back(next_state);
+                },
+            }
+        }
+    }
+
+    result
+}
+
+// The neighbours of a state come with the cost of moving to them
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    a_star(starts, neighbours, |_| 0, is_goal)
+}
+
+// The heuristic must never overestimate the remaining cost and must be consistent,
+// otherwise the returned cost is not guaranteed to be optimal
+pub fn a_star<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut result = SearchResult::new();
+
+    // States are kept aside so that the heap does not need them to be ordered
+    let mut queued_states = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if result.costs.insert(start.clone(), 0).is_none() {
+            result.starts.insert(start.clone());
+            heap.push(Reverse((heuristic(&start), 0, queued_states.len())));
+            queued_states.push(start);
+        }
+    }
+
+    while let Some(Reverse((estimated_cost, cost, index))) = heap.pop() {
+        if result.cost.is_some_and(|goal_cost| estimated_cost > goal_cost) {
+            break;
+        }
+
+        let state = queued_states[index].clone();
+
+        if result.costs[&state] < cost || !result.visited.insert(state.clone()) {
+            continue;
+        }
+
+        if is_goal(&state) {
+            result.reach_goal(state, cost);
+            continue;
+        }
+
+        for (next_state, step_cost) in neighbours(&state) {
+            let next_cost = cost + step_cost;
+
+            match result.costs.get(&next_state) {
+                Some(&known_cost) if known_cost < next_cost => {},
+                Some(&known_cost) if known_cost == next_cost => {
+                    result.predecessors.entry(next_state).or_default().push(state.clone());
+                },
+                _ => {
+                    result.costs.insert(next_state.clone(), next_cost);
+                    result.predecessors.insert(next_state.clone(), vec![state.clone()]);
+                    heap.push(Reverse((next_cost + heuristic(&next_state), next_cost, queued_states.len())));
+                    queued_states.push(next_state);
+                },
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{cardinal_directions, split_lines, Grid, Position};
+
+    const MAZE: &str = "\
+.....
+.###.
+...#.
+.#...";
+
+    fn maze() -> Grid<bool> {
+        Grid::from_text_lines(&split_lines(MAZE), |c| Some(c == '.')).unwrap()
+    }
+
+    fn free_neighbours(maze: &Grid<bool>, position: &Position) -> Vec<Position> {
+        maze
+            .neighbours(position, &cardinal_directions())
+            .filter(|(_, _, is_free)| **is_free)
+            .map(|(_, p, _)| p)
+            .collect()
+    }
+
+    fn corner() -> Position {
+        Position { x: 4, y: 3 }
+    }
+
+    #[test]
+    fn test_breadth_first_search() {
+        let maze = maze();
+        let result = breadth_first_search([Position { x: 0, y: 0 }], |p| free_neighbours(&maze, p), |p| *p == corner());
+
+        assert_eq!(result.cost(), Some(7));
+        assert_eq!(result.path().unwrap().len(), 8);
+        assert_eq!(result.optimal_path_count(&corner()), 2);
+        assert_eq!(result.states_on_optimal_paths().len(), 14);
+    }
+
+    #[test]
+    fn test_breadth_first_search_without_goal_visits_everything() {
+        let maze = maze();
+        let result = breadth_first_search([Position { x: 0, y: 0 }], |p| free_neighbours(&maze, p), |_| false);
+
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.visited().count(), 15);
+        assert_eq!(result.cost_to(&corner()), Some(7));
+        assert_eq!(result.cost_to(&Position { x: 1, y: 1 }), None);
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheap_steps() {
+        // Moving down the first column costs 10, so only the way along the top row is optimal
+        let maze = maze();
+        let weighted_neighbours = |p: &Position| {
+            let position = *p;
+
+            free_neighbours(&maze, p)
+                .into_iter()
+                .map(move |n| (n, if position.x == 0 && n.y > position.y { 10 } else { 1 }))
+                .collect::<Vec<_>>()
+        };
+
+        let result = dijkstra([Position { x: 0, y: 0 }], weighted_neighbours, |p| *p == corner());
+
+        assert_eq!(result.cost(), Some(7));
+        assert_eq!(result.path().unwrap()[1], Position { x: 1, y: 0 });
+        assert_eq!(result.predecessors(&corner()), &[Position { x: 4, y: 2 }]);
+    }
+
+    #[test]
+    fn test_path_through_zero_cost_cycle() {
+        // 0 and 1 are connected both ways for free, so the start picks up 1 as a predecessor
+        let neighbours = |state: &u32| match state {
+            0 => vec![(1, 0)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+
+        let result = dijkstra([0], neighbours, |state| *state == 2);
+
+        assert_eq!(result.cost(), Some(1));
+        assert_eq!(result.predecessors(&0), &[1]);
+        assert_eq!(result.path(), Some(vec![0, 1, 2]));
+        assert_eq!(result.path_to(&0), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_a_star_matches_dijkstra() {
+        let maze = maze();
+        let neighbours = |p: &Position| free_neighbours(&maze, p).into_iter().map(|n| (n, 1)).collect::<Vec<_>>();
+        let heuristic = |p: &Position| corner().x.abs_diff(p.x) + corner().y.abs_diff(p.y);
+
+        let a_star_result = a_star([Position { x: 0, y: 0 }], neighbours, heuristic, |p| *p == corner());
+        let dijkstra_result = dijkstra([Position { x: 0, y: 0 }], neighbours, |p| *p == corner());
+
+        assert_eq!(a_star_result.cost(), dijkstra_result.cost());
+        assert_eq!(a_star_result.optimal_path_count(&corner()), 2);
+        assert!(a_star_result.visited().count() <= dijkstra_result.visited().count());
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let maze = maze();
+        let result = dijkstra(
+            [Position { x: 0, y: 0 }],
+            |p| free_neighbours(&maze, p).into_iter().map(|n| (n, 1)).collect::<Vec<_>>(),
+            |p| *p == Position { x: 1, y: 1 },
+        );
+
+        assert_eq!(result.cost(), None);
+        assert_eq!(result.path(), None);
+        assert!(result.states_on_optimal_paths().is_empty());
+    }
+}
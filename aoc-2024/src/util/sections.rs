@@ -0,0 +1,134 @@
+use crate::error::{AocError, AocResult};
+
+// A block of consecutive non-blank lines of the input
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Section<'a> {
+    index: usize,
+    first_line_index: usize,
+    lines: Vec<&'a str>,
+}
+
+impl<'a> Section<'a> {
+    // 0-based position among the sections of the input
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    // 0-based line index of the first line of the section in the whole input
+    pub fn first_line_index(&self) -> usize {
+        self.first_line_index
+    }
+
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    // Parses the section as a whole, line numbers in errors are counted from the start of the section
+    pub fn parse<T>(&self, parse_section: impl FnOnce(&[&'a str]) -> AocResult<T>) -> AocResult<T> {
+        parse_section(&self.lines).map_err(|e| self.locate(e))
+    }
+
+    pub fn parse_lines<T>(&self, parse_line: impl Fn(&str) -> AocResult<T>) -> AocResult<Vec<T>> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| parse_line(line).map_err(|e| self.locate(e.at_line(index, line))))
+            .collect()
+    }
+
+    fn locate(&self, error: AocError) -> AocError {
+        error.with_line_offset(self.first_line_index).in_section(self.index)
+    }
+}
+
+// Blank lines separate sections, several blank lines in a row count as one
+pub fn split_sections(text: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut previous_line_was_blank = true;
+
+    for (line_index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            previous_line_was_blank = true;
+            continue;
+        }
+
+        match (previous_line_was_blank, sections.last_mut()) {
+            (false, Some(section)) => section.lines.push(line),
+            _ => sections.push(Section { index: sections.len(), first_line_index: line_index, lines: vec![line] }),
+        }
+
+        previous_line_was_blank = false;
+    }
+
+    sections
+}
+
+// For inputs with a known layout, e.g. a map followed by a list of moves
+pub fn split_fixed_sections<const N: usize>(text: &str) -> AocResult<[Section<'_>; N]> {
+    let sections = split_sections(text);
+    let number_of_sections = sections.len();
+
+    sections
+        .try_into()
+        .map_err(|_| AocError::parse(format!("Expected {} sections separated by blank lines, found {}", N, number_of_sections)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+
+    const TEXT: &str = "\n1\n2\n\n\n3\n  \n4\n5\n";
+
+    fn parse_number(line: &str) -> AocResult<i32> {
+        line.parse::<i32>().map_err(|e| AocError::parse(e.to_string()))
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let sections = split_sections(TEXT);
+
+        assert_eq!(sections.iter().map(|s| s.lines().to_vec()).collect::<Vec<_>>(), vec![vec!["1", "2"], vec!["3"], vec!["4", "5"]]);
+        assert_eq!(sections.iter().map(|s| s.first_line_index()).collect::<Vec<_>>(), vec![1, 5, 7]);
+        assert_eq!(sections[2].index(), 2);
+        assert_eq!(sections[2].text(), "4\n5");
+        assert!(split_sections("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_split_fixed_sections() {
+        let [first, _, third] = split_fixed_sections(TEXT).unwrap();
+
+        assert_eq!(first.parse_lines(parse_number).unwrap(), vec![1, 2]);
+        assert_eq!(third.parse(|lines| Ok(lines.len())).unwrap(), 2);
+        assert!(split_fixed_sections::<2>(TEXT).is_err());
+    }
+
+    #[test]
+    fn test_errors_name_section_and_line() {
+        let [_, second] = split_fixed_sections("1\n2\n\n3\nfour").unwrap();
+
+        let Err(AocError::Parse(parse_error)) = second.parse_lines(parse_number) else {
+            panic!("Expected a parse error");
+        };
+
+        assert_eq!(parse_error.section, Some(2));
+        assert_eq!(parse_error.line_number, Some(5));
+        assert_eq!(parse_error.line, Some("four".to_string()));
+    }
+
+    #[test]
+    fn test_whole_section_errors_are_moved_to_the_input() {
+        let [_, second] = split_fixed_sections("1\n\n2\nthree").unwrap();
+
+        let result = second.parse(|lines| {
+            lines.iter().enumerate().map(|(i, l)| parse_number(l).map_err(|e| e.at_line(i, l))).collect::<AocResult<Vec<i32>>>()
+        });
+
+        assert!(matches!(result, Err(AocError::Parse(ParseError { line_number: Some(4), section: Some(2), .. }))));
+    }
+}
@@ -0,0 +1,209 @@
+use crate::util::{least_common_multiple, Dimensions, Direction, Grid, Position, Vector};
+
+// A map whose opposite edges are glued together, walking off one side comes back in on the other
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Torus {
+    width: usize,
+    height: usize,
+}
+
+impl Torus {
+    // A torus needs at least one cell
+    pub fn new(width: usize, height: usize) -> Option<Self> {
+        match width > 0 && height > 0 {
+            true => Some(Torus { width, height }),
+            false => None,
+        }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        Dimensions { width: self.width, height: self.height }
+    }
+
+    pub fn wrap(&self, x: i64, y: i64) -> Position {
+        Position {
+            x: x.rem_euclid(self.width as i64) as usize,
+            y: y.rem_euclid(self.height as i64) as usize,
+        }
+    }
+
+    pub fn step(&self, position: &Position, vector: &Vector<i64>) -> Position {
+        self.step_repeatedly(position, vector, 1)
+    }
+
+    // Same as taking `steps` single steps, without overflowing for large step counts
+    pub fn step_repeatedly(&self, position: &Position, vector: &Vector<i64>, steps: usize) -> Position {
+        let (width, height) = (self.width as i64, self.height as i64);
+
+        let dx = vector.dx.rem_euclid(width) * (steps as i64 % width);
+        let dy = vector.dy.rem_euclid(height) * (steps as i64 % height);
+
+        self.wrap(position.x as i64 + dx, position.y as i64 + dy)
+    }
+
+    pub fn walk_in_direction(&self, position: &Position, direction: &Direction) -> Position {
+        let movement = direction.get_movement();
+
+        self.step(position, &Vector::new(movement.dx as i64, movement.dy as i64))
+    }
+
+    pub fn neighbours<'a>(
+        &'a self,
+        position: &'a Position,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = (Direction, Position)> + 'a {
+        directions.iter().map(move |d| (*d, self.walk_in_direction(position, d)))
+    }
+
+    // Shortest distance in each axis, going either way round
+    pub fn manhattan_distance(&self, first: &Position, second: &Position) -> usize {
+        let (dx, dy) = self.axis_distances(first, second);
+
+        dx + dy
+    }
+
+    pub fn chebyshev_distance(&self, first: &Position, second: &Position) -> usize {
+        let (dx, dy) = self.axis_distances(first, second);
+
+        dx.max(dy)
+    }
+
+    // Any movement with a constant velocity returns to its start after this many steps
+    pub fn period(&self) -> usize {
+        least_common_multiple(self.width, self.height)
+    }
+
+    fn axis_distances(&self, first: &Position, second: &Position) -> (usize, usize) {
+        let dx = first.x.abs_diff(second.x) % self.width;
+        let dy = first.y.abs_diff(second.y) % self.height;
+
+        (dx.min(self.width - dx), dy.min(self.height - dy))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Particle {
+    pub position: Position,
+    pub velocity: Vector<i64>,
+}
+
+// Bodies moving with constant velocities on a torus, every step count is computed directly
+pub struct ParticleSimulation {
+    torus: Torus,
+    particles: Vec<Particle>,
+}
+
+impl ParticleSimulation {
+    pub fn new(torus: Torus, particles: Vec<Particle>) -> Self {
+        ParticleSimulation { torus, particles }
+    }
+
+    pub fn torus(&self) -> &Torus {
+        &self.torus
+    }
+
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    pub fn period(&self) -> usize {
+        self.torus.period()
+    }
+
+    pub fn positions_after(&self, steps: usize) -> Vec<Position> {
+        self.particles
+            .iter()
+            .map(|p| self.torus.step_repeatedly(&p.position, &p.velocity, steps))
+            .collect()
+    }
+
+    pub fn advance(&mut self, steps: usize) {
+        for particle in self.particles.iter_mut() {
+            particle.position = self.torus.step_repeatedly(&particle.position, &particle.velocity, steps);
+        }
+    }
+
+    // Number of particles in each cell after the given number of steps
+    pub fn occupancy_after(&self, steps: usize) -> Grid<usize> {
+        let mut occupancy = Grid::filled(self.torus.width, self.torus.height, 0);
+
+        for position in self.positions_after(steps) {
+            occupancy[position] += 1;
+        }
+
+        occupancy
+    }
+
+    // One occupancy grid per step, covering a whole period
+    pub fn occupancies(&self) -> impl Iterator<Item = (usize, Grid<usize>)> + '_ {
+        (0..self.period()).map(|steps| (steps, self.occupancy_after(steps)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::cardinal_directions;
+    use test_case::test_case;
+
+    fn torus() -> Torus {
+        Torus::new(11, 7).unwrap()
+    }
+
+    #[test]
+    fn test_neighbours_wrap() {
+        let torus = torus();
+
+        let neighbours = torus
+            .neighbours(&Position { x: 0, y: 6 }, &cardinal_directions())
+            .map(|(_, p)| p)
+            .collect::<Vec<Position>>();
+
+        assert_eq!(
+            neighbours,
+            vec![Position { x: 0, y: 5 }, Position { x: 1, y: 6 }, Position { x: 0, y: 0 }, Position { x: 10, y: 6 }],
+        );
+    }
+
+    #[test_case(Position { x: 0, y: 0 }, Position { x: 10, y: 6 }, 2, 1)]
+    #[test_case(Position { x: 2, y: 3 }, Position { x: 7, y: 3 }, 5, 5)]
+    #[test_case(Position { x: 1, y: 1 }, Position { x: 1, y: 1 }, 0, 0)]
+    fn test_distances(first: Position, second: Position, expected_manhattan: usize, expected_chebyshev: usize) {
+        assert_eq!(torus().manhattan_distance(&first, &second), expected_manhattan);
+        assert_eq!(torus().chebyshev_distance(&first, &second), expected_chebyshev);
+    }
+
+    #[test]
+    fn test_step_repeatedly_matches_single_steps() {
+        let torus = torus();
+        let velocity = Vector::new(2, -3);
+        let mut position = Position { x: 2, y: 4 };
+
+        for _ in 0..5 {
+            position = torus.step(&position, &velocity);
+        }
+
+        assert_eq!(position, Position { x: 1, y: 3 });
+        assert_eq!(torus.step_repeatedly(&Position { x: 2, y: 4 }, &velocity, 5), position);
+        assert_eq!(torus.step_repeatedly(&Position { x: 2, y: 4 }, &velocity, 5 + 77 * 1_000_000_000), position);
+    }
+
+    #[test]
+    fn test_particle_simulation() {
+        let particle = Particle { position: Position { x: 2, y: 4 }, velocity: Vector::new(2, -3) };
+        let mut simulation = ParticleSimulation::new(torus(), vec![particle, particle]);
+
+        assert_eq!(simulation.period(), 77);
+        assert_eq!(simulation.positions_after(simulation.period()), vec![particle.position; 2]);
+        assert_eq!(simulation.occupancy_after(5)[Position { x: 1, y: 3 }], 2);
+        assert_eq!(simulation.occupancies().count(), 77);
+
+        simulation.advance(5);
+        assert_eq!(simulation.particles()[0].position, Position { x: 1, y: 3 });
+    }
+
+    #[test]
+    fn test_empty_torus() {
+        assert_eq!(Torus::new(0, 5), None);
+    }
+}
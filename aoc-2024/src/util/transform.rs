@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use crate::util::{Dimensions, Grid, Position};
+
+// The eight ways of turning and mirroring a rectangular map, rotations are clockwise
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Transformation {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Transformation {
+    pub fn all() -> [Transformation; 8] {
+        [
+            Transformation::Identity,
+            Transformation::Rotate90,
+            Transformation::Rotate180,
+            Transformation::Rotate270,
+            Transformation::FlipHorizontal,
+            Transformation::FlipVertical,
+            Transformation::Transpose,
+            Transformation::AntiTranspose,
+        ]
+    }
+
+    pub fn rotations() -> [Transformation; 4] {
+        [Transformation::Identity, Transformation::Rotate90, Transformation::Rotate180, Transformation::Rotate270]
+    }
+
+    // Returns None unless the angle is a multiple of 90 degrees
+    pub fn rotation(degrees: u32) -> Option<Self> {
+        match degrees % 360 {
+            0 => Some(Transformation::Identity),
+            90 => Some(Transformation::Rotate90),
+            180 => Some(Transformation::Rotate180),
+            270 => Some(Transformation::Rotate270),
+            _ => None,
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        match self {
+            Transformation::Rotate90 => Transformation::Rotate270,
+            Transformation::Rotate270 => Transformation::Rotate90,
+            _ => *self,
+        }
+    }
+
+    pub fn transformed_dimensions(&self, dimensions: &Dimensions) -> Dimensions {
+        match self {
+            Transformation::Rotate90 | Transformation::Rotate270 | Transformation::Transpose | Transformation::AntiTranspose => {
+                Dimensions { width: dimensions.height, height: dimensions.width }
+            },
+            _ => *dimensions,
+        }
+    }
+
+    // Where a position of a map with the given dimensions ends up after the transformation
+    pub fn apply_to_position(&self, position: &Position, dimensions: &Dimensions) -> Position {
+        let (x, y) = (position.x, position.y);
+        let (last_x, last_y) = (dimensions.width - 1, dimensions.height - 1);
+
+        match self {
+            Transformation::Identity => Position { x, y },
+            Transformation::Rotate90 => Position { x: last_y - y, y: x },
+            Transformation::Rotate180 => Position { x: last_x - x, y: last_y - y },
+            Transformation::Rotate270 => Position { x: y, y: last_x - x },
+            Transformation::FlipHorizontal => Position { x: last_x - x, y },
+            Transformation::FlipVertical => Position { x, y: last_y - y },
+            Transformation::Transpose => Position { x: y, y: x },
+            Transformation::AntiTranspose => Position { x: last_y - y, y: last_x - x },
+        }
+    }
+}
+
+// Operations shared by dense grids and sparse position maps
+pub trait Transformable: Sized {
+    fn transformed(&self, transformation: Transformation) -> Self;
+
+    // The part of the map with the given top left corner and size, positions start again at (0, 0)
+    fn window(&self, top_left: &Position, width: usize, height: usize) -> Option<Self>;
+
+    // Every window of the given size that fits, along with its top left corner, in row-major order.
+    // Windows are copied one at a time as the iterator is advanced.
+    fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = (Position, Self)> + '_;
+
+    fn rotated(&self, degrees: u32) -> Option<Self> {
+        Transformation::rotation(degrees).map(|t| self.transformed(t))
+    }
+
+    fn flipped_horizontally(&self) -> Self {
+        self.transformed(Transformation::FlipHorizontal)
+    }
+
+    fn flipped_vertically(&self) -> Self {
+        self.transformed(Transformation::FlipVertical)
+    }
+
+    fn transposed(&self) -> Self {
+        self.transformed(Transformation::Transpose)
+    }
+}
+
+impl<T: Clone> Transformable for Grid<T> {
+    fn transformed(&self, transformation: Transformation) -> Self {
+        let dimensions = transformation.transformed_dimensions(&self.dimensions());
+        let inverse = transformation.inverse();
+
+        let cells = (0..dimensions.height)
+            .flat_map(|y| (0..dimensions.width).map(move |x| Position { x, y }))
+            .map(|p| self[inverse.apply_to_position(&p, &dimensions)].clone())
+            .collect();
+
+        Grid::new(dimensions.width, dimensions.height, cells).unwrap()
+    }
+
+    fn window(&self, top_left: &Position, width: usize, height: usize) -> Option<Self> {
+        self.window_view(top_left, width, height).map(|w| w.to_grid())
+    }
+
+    fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = (Position, Self)> + '_ {
+        self.window_views(width, height).map(|w| (w.top_left(), w.to_grid()))
+    }
+}
+
+// Part of a grid without copying its cells, positions are relative to the top left corner of the window
+pub struct GridWindow<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: Position,
+    dimensions: Dimensions,
+}
+
+impl<'a, T> GridWindow<'a, T> {
+    pub fn top_left(&self) -> Position {
+        self.top_left
+    }
+
+    pub fn width(&self) -> usize {
+        self.dimensions.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.dimensions.height
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&'a T> {
+        if !self.dimensions.includes(position) {
+            return None;
+        }
+
+        self.grid.get(&Position { x: self.top_left.x + position.x, y: self.top_left.y + position.y })
+    }
+
+    // Row-major order, like Grid::iter
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &'a T)> + '_ {
+        (0..self.height())
+            .flat_map(move |y| (0..self.width()).map(move |x| Position { x, y }))
+            .filter_map(move |p| self.get(&p).map(|v| (p, v)))
+    }
+
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = self.iter().map(|(_, v)| v.clone()).collect();
+
+        Grid::new(self.width(), self.height(), cells).unwrap()
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn window_view(&self, top_left: &Position, width: usize, height: usize) -> Option<GridWindow<'_, T>> {
+        if top_left.x + width > self.width() || top_left.y + height > self.height() {
+            return None;
+        }
+
+        Some(GridWindow { grid: self, top_left: *top_left, dimensions: Dimensions { width, height } })
+    }
+
+    // Every window of the given size that fits, in row-major order
+    pub fn window_views(&self, width: usize, height: usize) -> impl Iterator<Item = GridWindow<'_, T>> {
+        window_corners(&self.dimensions(), width, height).filter_map(move |p| self.window_view(&p, width, height))
+    }
+}
+
+// Sparse maps are taken to span from (0, 0) up to their largest position
+impl<T: Clone> Transformable for HashMap<Position, T> {
+    fn transformed(&self, transformation: Transformation) -> Self {
+        let dimensions = map_dimensions(self);
+
+        self.iter().map(|(p, v)| (transformation.apply_to_position(p, &dimensions), v.clone())).collect()
+    }
+
+    fn window(&self, top_left: &Position, width: usize, height: usize) -> Option<Self> {
+        let dimensions = map_dimensions(self);
+
+        if top_left.x + width > dimensions.width || top_left.y + height > dimensions.height {
+            return None;
+        }
+
+        let window_dimensions = Dimensions { width, height };
+
+        Some(self
+            .iter()
+            .filter_map(|(p, v)| {
+                let position = Position { x: p.x.checked_sub(top_left.x)?, y: p.y.checked_sub(top_left.y)? };
+
+                window_dimensions.includes(&position).then(|| (position, v.clone()))
+            })
+            .collect())
+    }
+
+    fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = (Position, Self)> + '_ {
+        window_corners(&map_dimensions(self), width, height)
+            .filter_map(move |p| self.window(&p, width, height).map(|w| (p, w)))
+    }
+}
+
+fn map_dimensions<T>(map: &HashMap<Position, T>) -> Dimensions {
+    Dimensions {
+        width: map.keys().map(|p| p.x + 1).max().unwrap_or(0),
+        height: map.keys().map(|p| p.y + 1).max().unwrap_or(0),
+    }
+}
+
+fn window_corners(dimensions: &Dimensions, width: usize, height: usize) -> impl Iterator<Item = Position> {
+    let x_count = (dimensions.width + 1).saturating_sub(width);
+    let y_count = (dimensions.height + 1).saturating_sub(height);
+
+    (0..y_count).flat_map(move |y| (0..x_count).map(move |x| Position { x, y }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::split_lines;
+    use test_case::test_case;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::from_text_lines(&split_lines(text), Some).unwrap()
+    }
+
+    fn text(grid: &Grid<char>) -> String {
+        grid.rows().map(|r| r.iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+    }
+
+    #[test_case(Transformation::Identity, "abc\ndef")]
+    #[test_case(Transformation::Rotate90, "da\neb\nfc")]
+    #[test_case(Transformation::Rotate180, "fed\ncba")]
+    #[test_case(Transformation::Rotate270, "cf\nbe\nad")]
+    #[test_case(Transformation::FlipHorizontal, "cba\nfed")]
+    #[test_case(Transformation::FlipVertical, "def\nabc")]
+    #[test_case(Transformation::Transpose, "ad\nbe\ncf")]
+    #[test_case(Transformation::AntiTranspose, "fc\neb\nda")]
+    fn test_transformed(transformation: Transformation, expected_text: &str) {
+        assert_eq!(text(&grid("abc\ndef").transformed(transformation)), expected_text);
+    }
+
+    #[test]
+    fn test_inverse_restores_grid() {
+        let original = grid("abc\ndef");
+
+        for transformation in Transformation::all() {
+            assert_eq!(original.transformed(transformation).transformed(transformation.inverse()), original);
+        }
+    }
+
+    #[test]
+    fn test_rotated() {
+        let original = grid("ab\ncd");
+
+        assert_eq!(original.rotated(90).map(|g| text(&g)), Some("ca\ndb".to_string()));
+        assert_eq!(original.rotated(45), None);
+    }
+
+    #[test]
+    fn test_window() {
+        let original = grid("abcd\nefgh\nijkl");
+
+        assert_eq!(original.window(&Position { x: 1, y: 1 }, 3, 2).map(|g| text(&g)), Some("fgh\njkl".to_string()));
+        assert_eq!(original.window(&Position { x: 2, y: 1 }, 3, 2), None);
+    }
+
+    #[test]
+    fn test_windows() {
+        let windows = grid("abcd\nefgh\nijkl").windows(2, 2).collect::<Vec<_>>();
+
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[5].0, Position { x: 2, y: 1 });
+        assert_eq!(text(&windows[5].1), "gh\nkl");
+        assert_eq!(grid("ab").windows(3, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_window_views_borrow_the_grid() {
+        let grid = grid("abcd\nefgh\nijkl");
+        let windows = grid.window_views(3, 2).collect::<Vec<GridWindow<char>>>();
+
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[3].top_left(), Position { x: 1, y: 1 });
+        assert_eq!(windows[3].iter().map(|(_, c)| *c).collect::<String>(), "fghjkl");
+        assert_eq!(windows[3].get(&Position { x: 2, y: 0 }), Some(&'h'));
+        assert_eq!(windows[3].get(&Position { x: 3, y: 0 }), None);
+        assert!(grid.window_view(&Position { x: 3, y: 2 }, 2, 1).is_none());
+    }
+
+    #[test]
+    fn test_sparse_map() {
+        let map = HashMap::from([(Position { x: 0, y: 0 }, 1), (Position { x: 2, y: 1 }, 2)]);
+
+        let rotated = map.rotated(90).unwrap();
+        assert_eq!(rotated, HashMap::from([(Position { x: 1, y: 0 }, 1), (Position { x: 0, y: 2 }, 2)]));
+
+        let window = map.window(&Position { x: 1, y: 1 }, 2, 1).unwrap();
+        assert_eq!(window, HashMap::from([(Position { x: 1, y: 0 }, 2)]));
+
+        assert_eq!(map.windows(2, 2).count(), 2);
+    }
+}
@@ -0,0 +1,46 @@
+use aoc_2024::dec_01::Dec01;
+use aoc_2024::registry;
+use aoc_2024::solution::{Part, Solution};
+use aoc_2024::util::{
+    get_position_map_dimensions, position_map_from_text_lines, positions_on_map_with_value, split_lines, Dimensions,
+    Direction, Grid, Position,
+};
+
+#[test]
+fn test_solve_day_through_solution() {
+    let input = Dec01.parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3").unwrap();
+
+    assert_eq!(Dec01.part_one(&input).unwrap(), 11);
+    assert_eq!(Dec01.part_two(&input).unwrap(), 31);
+}
+
+#[test]
+fn test_solve_day_through_registry() {
+    let day = registry::find_day(1).unwrap();
+
+    assert_eq!(day.solve(Part::One, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3").unwrap(), 11);
+}
+
+#[test]
+fn test_util_is_reusable() {
+    let lines = split_lines("#.\n.#");
+    let position_map = position_map_from_text_lines(&lines, |c| Some(c == '#')).unwrap();
+
+    let start = Position { x: 0, y: 0 };
+
+    assert_eq!(position_map.get(&start), Some(&true));
+    assert_eq!(start.walk_in_direction(&Direction::SE), Some(Position { x: 1, y: 1 }));
+}
+
+#[test]
+fn test_position_map_helpers_agree_with_grid() {
+    let lines = split_lines("#.\n.#");
+    let position_map = position_map_from_text_lines(&lines, |c| Some(c == '#')).unwrap();
+    let grid = Grid::from_text_lines(&lines, |c| Some(c == '#')).unwrap();
+
+    let mut map_positions = positions_on_map_with_value(&position_map, true);
+    map_positions.sort_by_key(|p| (p.y, p.x));
+
+    assert_eq!(get_position_map_dimensions(&position_map), Some(Dimensions { width: 2, height: 2 }));
+    assert_eq!(map_positions, grid.positions_of(&true));
+}